@@ -1,32 +1,98 @@
-use crate::core::config;
+use crate::core::config::{self, ValueKind};
 use crate::core::paths;
 use crate::error::AppError;
 use std::env;
 use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
 /// Subcommands supported by `fusion <service> config`.
 #[derive(Debug)]
 pub enum ServiceConfigCommand {
-    Show,
+    Show {
+        format: ConfigFormat,
+    },
     Edit,
-    Path,
+    /// Print the config file path, or its directory with `dir`; `ensure` creates the file first.
+    Path {
+        ensure: bool,
+        dir: bool,
+    },
     Reset,
+    Diff,
+    ListKeys,
+    Set {
+        key: String,
+        value: String,
+        kind: Option<ValueKind>,
+    },
+    Import {
+        path: PathBuf,
+        force: bool,
+    },
+    /// Write the config to `path`, or to stdout when `path` is `-`.
+    Export {
+        path: PathBuf,
+    },
+    Migrate,
+    /// Print a JSON Schema for the config file, for editor completion.
+    Schema,
 }
 
 pub fn handle_config(command: ServiceConfigCommand) -> Result<(), AppError> {
     match command {
-        ServiceConfigCommand::Show => show_config(),
+        ServiceConfigCommand::Show { format } => show_config(format),
         ServiceConfigCommand::Edit => edit_config(),
-        ServiceConfigCommand::Path => print_config_path(),
+        ServiceConfigCommand::Path { ensure, dir } => print_config_path(ensure, dir),
         ServiceConfigCommand::Reset => reset_config(),
+        ServiceConfigCommand::Diff => diff_config(),
+        ServiceConfigCommand::ListKeys => list_config_keys(),
+        ServiceConfigCommand::Schema => print_config_schema(),
+        ServiceConfigCommand::Set { key, value, kind } => set_config(&key, &value, kind),
+        ServiceConfigCommand::Import { path, force } => import_config(&path, force),
+        ServiceConfigCommand::Export { path } => export_config(&path),
+        ServiceConfigCommand::Migrate => migrate_config(),
     }
 }
 
-fn show_config() -> Result<(), AppError> {
-    let _ = config::load_config_document()?;
-    let path = paths::user_config_file()?;
-    let contents = fs::read_to_string(&path)?;
-    print!("{}", contents);
+/// Output format accepted by `fusion config show --format`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConfigFormat {
+    /// The config file exactly as written, comments included.
+    #[default]
+    Toml,
+    /// The typed configuration, defaults filled in, as pretty JSON.
+    Json,
+}
+
+impl FromStr for ConfigFormat {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "toml" => Ok(ConfigFormat::Toml),
+            "json" => Ok(ConfigFormat::Json),
+            other => Err(format!("unknown format '{other}' (expected toml or json)")),
+        }
+    }
+}
+
+fn show_config(format: ConfigFormat) -> Result<(), AppError> {
+    match format {
+        ConfigFormat::Toml => {
+            let _ = config::load_config_document()?;
+            let path = paths::user_config_file()?;
+            let contents = fs::read_to_string(&path)?;
+            print!("{}", contents);
+        }
+        ConfigFormat::Json => {
+            let cfg = config::load_config()?;
+            let json = serde_json::to_string_pretty(&cfg)
+                .map_err(|err| AppError::config_error(format!("cannot render config: {err}")))?;
+            println!("{json}");
+        }
+    }
     Ok(())
 }
 
@@ -73,9 +139,13 @@ fn edit_config() -> Result<(), AppError> {
     Ok(())
 }
 
-fn print_config_path() -> Result<(), AppError> {
+fn print_config_path(ensure: bool, dir: bool) -> Result<(), AppError> {
+    if ensure {
+        config::ensure_config_exists()?;
+    }
     let path = paths::user_config_file()?;
-    println!("{}", path.display());
+    let shown = if dir { path.parent().unwrap_or(&path) } else { &path };
+    println!("{}", shown.display());
     Ok(())
 }
 
@@ -95,3 +165,86 @@ fn reset_config() -> Result<(), AppError> {
     println!("Created new config file with default values: {}", path.display());
     Ok(())
 }
+
+fn diff_config() -> Result<(), AppError> {
+    let current = config::load_config()?;
+    let differences = config::diff_configs(&config::Config::default(), &current)?;
+    if differences.is_empty() {
+        println!("No differences from the default configuration.");
+        return Ok(());
+    }
+    for difference in differences {
+        println!(
+            "{}: {} -> {}",
+            difference.key,
+            display_value(difference.default.as_ref()),
+            display_value(difference.current.as_ref())
+        );
+    }
+    Ok(())
+}
+
+fn display_value(value: Option<&toml::Value>) -> String {
+    value.map(ToString::to_string).unwrap_or_else(|| "(unset)".to_string())
+}
+
+fn list_config_keys() -> Result<(), AppError> {
+    let current = config::load_config_document()?;
+    let defaults = config::default_config_document()?;
+    for leaf in config::document_leaves(&defaults) {
+        let value = config::document_value(&current, &leaf.key)
+            .map(|value| value.to_string().trim().to_string())
+            .unwrap_or_else(|| "(unset)".to_string());
+        println!("{} = {} ({})", leaf.key, value, config::value_type_name(&leaf.value));
+    }
+    Ok(())
+}
+
+fn print_config_schema() -> Result<(), AppError> {
+    let schema = config::config_json_schema()?;
+    let json = serde_json::to_string_pretty(&schema)
+        .map_err(|err| AppError::config_error(format!("cannot render schema: {err}")))?;
+    println!("{json}");
+    Ok(())
+}
+
+fn set_config(key: &str, value: &str, kind: Option<ValueKind>) -> Result<(), AppError> {
+    config::set_config_value(key, value, kind)?;
+    println!("Set {key} = {value}");
+    Ok(())
+}
+
+fn import_config(path: &Path, force: bool) -> Result<(), AppError> {
+    let contents = fs::read_to_string(path)?;
+    let target = paths::user_config_file()?;
+    if config::import_config(&contents, force)? {
+        println!("Imported {} into {}", path.display(), target.display());
+    } else {
+        println!("{} already matches {}", target.display(), path.display());
+    }
+    Ok(())
+}
+
+fn migrate_config() -> Result<(), AppError> {
+    let path = paths::user_config_file()?;
+    match config::migrate_config()? {
+        Some(previous) => println!(
+            "Migrated {} from version {previous} to {}",
+            path.display(),
+            config::CONFIG_VERSION
+        ),
+        None => println!("{} is already at version {}", path.display(), config::CONFIG_VERSION),
+    }
+    Ok(())
+}
+
+fn export_config(path: &Path) -> Result<(), AppError> {
+    let contents = config::export_config()?;
+    if path == Path::new("-") {
+        io::stdout().write_all(contents.as_bytes())?;
+    } else {
+        fs::write(path, contents)?;
+        println!("Exported config to {}", path.display());
+    }
+    Ok(())
+}
@@ -0,0 +1,25 @@
+use super::style;
+use crate::core::doctor::{self, CheckStatus};
+use crate::error::AppError;
+
+pub fn handle_doctor() -> Result<(), AppError> {
+    println!("🩺 Checking fusion environment:");
+    let results = doctor::run_checks();
+    for result in &results {
+        let label = match result.status {
+            CheckStatus::Pass => style::green("pass"),
+            CheckStatus::Warn => "warn".to_string(),
+            CheckStatus::Fail => style::red("fail"),
+        };
+        println!("• [{label}] {}: {}", result.name, result.detail);
+    }
+
+    let failures = results.iter().filter(|result| result.status == CheckStatus::Fail).count();
+    if failures > 0 {
+        return Err(AppError::checks_failed(format!(
+            "{failures} check{} failed",
+            if failures == 1 { "" } else { "s" }
+        )));
+    }
+    Ok(())
+}
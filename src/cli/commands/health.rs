@@ -2,11 +2,29 @@ use super::shared::{load_config, service_for_runtime};
 use crate::cli::ServiceType;
 use crate::core::health;
 use crate::error::AppError;
+use serde::Serialize;
+use std::time::{Duration, Instant};
 
 /// Allow a slightly longer timeout for inference (considering model load time)
 const HEALTH_TIMEOUT_SECS: u64 = 30;
+/// Pings only wait for a connection and response headers, so keep them short.
+const PING_TIMEOUT_MS: u64 = 2000;
 
-pub fn handle_health_single(service_type: ServiceType) -> Result<(), AppError> {
+/// Result of `health --json`, one object for CI gates.
+#[derive(Debug, Serialize)]
+struct HealthReport<'a> {
+    service: &'a str,
+    healthy: bool,
+    latency_ms: u64,
+    model: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Run a minimal inference request; with `json`, print a [`HealthReport`] instead of the text lines.
+pub fn handle_health_single(service_type: ServiceType, json: bool) -> Result<(), AppError> {
     let cfg = load_config()?;
 
     let service = service_for_runtime(&cfg, service_type)?;
@@ -17,14 +35,40 @@ pub fn handle_health_single(service_type: ServiceType) -> Result<(), AppError> {
 
     let prompt = "Briefly introduce yourself in one sentence.";
 
-    println!("🩺 Checking {} health (inference test)...", service.name);
-    println!("   Model: {}", model_name);
-    println!("   Prompt: \"{}\"", prompt);
+    if !json {
+        println!("🩺 Checking {} health (inference test)...", service.name);
+        println!("   Model: {}", model_name);
+        println!("   Prompt: \"{}\"", prompt);
+    }
 
-    let response = health::query_inference(&service, &model_name, prompt, HEALTH_TIMEOUT_SECS)?;
+    let started = Instant::now();
+    let result = health::query_inference(&service, &model_name, prompt, HEALTH_TIMEOUT_SECS);
+    let latency_ms = started.elapsed().as_millis() as u64;
 
+    if json {
+        let report = HealthReport {
+            service: service.name,
+            healthy: result.is_ok(),
+            latency_ms,
+            model: &model_name,
+            response: result.as_ref().ok().map(|response| response.trim()),
+            error: result.as_ref().err().map(ToString::to_string),
+        };
+        println!("{}", serde_json::to_string(&report).expect("health report serializes"));
+        return result.map(|_| ());
+    }
+
+    let response = result?;
     println!("✅ {}: Healthy", service.name);
     println!("📝 Response: {}", response.trim());
 
     Ok(())
 }
+
+pub fn handle_ping(service_type: ServiceType) -> Result<(), AppError> {
+    let cfg = load_config()?;
+    let service = service_for_runtime(&cfg, service_type)?;
+    let latency = health::ping(&service, Duration::from_millis(PING_TIMEOUT_MS))?;
+    println!("✅ {} is up on {} ({} ms)", service.name, service.base_url(), latency.as_millis());
+    Ok(())
+}
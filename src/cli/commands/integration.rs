@@ -0,0 +1,65 @@
+use super::shared::{load_config, service_for_up};
+use crate::cli::ServiceType;
+use crate::core::integration;
+use crate::core::paths;
+use crate::error::AppError;
+use std::fs;
+use std::process::Command;
+
+pub fn handle_systemd(service_type: ServiceType, install: bool) -> Result<(), AppError> {
+    let cfg = load_config()?;
+    let service = service_for_up(&cfg, service_type);
+    let unit = integration::render_systemd_unit(&service);
+
+    if !install {
+        print!("{unit}");
+        return Ok(());
+    }
+
+    let dir = paths::systemd_user_dir()?;
+    fs::create_dir_all(&dir)?;
+    let unit_name = integration::systemd_unit_name(&service);
+    let unit_path = dir.join(&unit_name);
+    fs::write(&unit_path, unit)?;
+
+    println!("Installed systemd unit: {}", unit_path.display());
+    println!(
+        "Enable it with: systemctl --user daemon-reload && systemctl --user enable --now {unit_name}"
+    );
+    Ok(())
+}
+
+pub fn handle_launchd(
+    service_type: ServiceType,
+    install: bool,
+    load: bool,
+) -> Result<(), AppError> {
+    let cfg = load_config()?;
+    let service = service_for_up(&cfg, service_type);
+    let plist = integration::render_launchd_plist(&service);
+
+    if !install {
+        print!("{plist}");
+        return Ok(());
+    }
+
+    let dir = paths::launch_agents_dir()?;
+    fs::create_dir_all(&dir)?;
+    let plist_path = dir.join(format!("{}.plist", integration::launchd_label(&service)));
+    fs::write(&plist_path, plist)?;
+    println!("Installed launchd agent: {}", plist_path.display());
+
+    if load {
+        let status = Command::new("launchctl").arg("load").arg("-w").arg(&plist_path).status()?;
+        if !status.success() {
+            return Err(AppError::process_error(
+                service.name,
+                format!("launchctl load exited with {status}"),
+            ));
+        }
+        println!("Loaded {} with launchctl.", integration::launchd_label(&service));
+    } else {
+        println!("Load it with: launchctl load -w {}", plist_path.display());
+    }
+    Ok(())
+}
@@ -1,33 +1,154 @@
 use super::shared::{load_config, service_for_runtime, service_for_up};
+use super::style;
 use crate::cli::{ServiceType, service_label};
-use crate::core::config::Config;
-use crate::core::health;
+use crate::core::config::{self, Config};
+use crate::core::health::{self, ReadinessError, ReadinessPoll, ReadinessProbe, WaitOutcome};
+use crate::core::log_stamp;
+use crate::core::metrics;
 use crate::core::paths;
-use crate::core::process::{self, StartOutcome, StatusOutcome, StopOutcome};
+use crate::core::process::{self, SignatureMatch, StartOutcome, StatusOutcome, StopOutcome};
 use crate::core::services::{self, ManagedService};
 use crate::error::AppError;
-use std::collections::VecDeque;
-use std::fs;
-use std::io;
+use serde::Serialize;
+use std::fs::{self, File};
+use std::io::{self, BufRead, IsTerminal, Read, Seek, SeekFrom, Write};
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Once;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::thread;
-use std::time::{Duration, Instant};
+use std::time::Duration;
 
 const LOG_TAIL_LINES: usize = 15;
-const DEFAULT_STARTUP_TIMEOUT_SECS: u64 = 300;
-const POLLING_INTERVAL_MS: u64 = 1000;
+
+/// Flags that adjust how `fusion <service> up` behaves.
+#[derive(Debug, Clone, Default)]
+pub struct UpOptions {
+    /// Print the resolved command and environment instead of spawning.
+    pub dry_run: bool,
+    /// Bind host for this launch only, overriding the configured value.
+    pub host: Option<String>,
+    /// Bind port for this launch only, overriding the configured value.
+    pub port: Option<u16>,
+    /// Extra `KEY=VALUE` environment for this launch, overriding config-derived entries.
+    pub env: Vec<(String, String)>,
+    /// Readiness timeout in seconds, overriding `FUSION_STARTUP_TIMEOUT_SECS`; 0 skips the wait.
+    pub timeout_secs: Option<u64>,
+    /// Spawn and print only the PID, without any readiness or liveness checks.
+    pub no_wait: bool,
+    /// Print a single JSON outcome object instead of the progress lines.
+    pub json: bool,
+    /// Acknowledge binding to every interface, silencing the public-bind warning.
+    pub allow_public_bind: bool,
+    /// Treat the port accepting connections as ready instead of requiring an inference reply.
+    pub wait_for_port: bool,
+    /// Echo the server log while waiting for readiness.
+    pub attach: bool,
+}
 
 pub fn handle_up(service_type: ServiceType) -> Result<(), AppError> {
-    println!("🚀 Starting {}...", service_label(service_type));
+    handle_up_with_options(service_type, &UpOptions::default())
+}
+
+pub fn handle_up_with_options(
+    service_type: ServiceType,
+    options: &UpOptions,
+) -> Result<(), AppError> {
+    let configured = load_config()?;
+    let mut cfg = configured.clone();
+    apply_bind_overrides(&mut cfg, service_type, options);
+    if let Some(warning) = services::check_startable(&cfg, service_type, options.allow_public_bind)?
+    {
+        eprintln!("⚠️  {warning}");
+    }
+    let mut service = service_for_up(&cfg, service_type);
+    // One-off bind overrides keep the configured state files so `down`/`ps` still find them.
+    service.use_state_files_of(&service_for_up(&configured, service_type));
+    service.merge_env(&options.env);
+    if options.dry_run {
+        print_dry_run(&service);
+        return Ok(());
+    }
+    if options.no_wait {
+        return spawn_without_waiting(&service, options.json);
+    }
+    if !options.json {
+        println!("🚀 Starting {}...", service_label(service_type));
+    }
+    let timeout_secs = options.timeout_secs.unwrap_or_else(health::startup_timeout_secs);
+    let probe =
+        if options.wait_for_port { ReadinessProbe::Port } else { ReadinessProbe::Inference };
+    let report =
+        handle_service_up(service, &cfg, timeout_secs, probe, options.json, options.attach)?;
+    if options.json {
+        report.print();
+    }
+    Ok(())
+}
+
+/// Apply one-off `--host`/`--port` values so the command, env, and runtime sidecar all agree.
+fn apply_bind_overrides(cfg: &mut Config, service_type: ServiceType, options: &UpOptions) {
+    let (host, port) = match service_type {
+        ServiceType::Ollama => (&mut cfg.ollama_server.host, &mut cfg.ollama_server.port),
+        ServiceType::Mlx => (&mut cfg.mlx_server.host, &mut cfg.mlx_server.port),
+    };
+    if let Some(override_host) = &options.host {
+        *host = override_host.clone();
+    }
+    if let Some(override_port) = options.port {
+        *port = override_port;
+    }
+}
+
+fn print_dry_run(service: &ManagedService) {
+    println!("🔎 Dry run for {} (nothing will be started)", service.name);
+    println!("• command: {}", service.command_line());
+    if service.env.is_empty() {
+        println!("• env: (none)");
+    } else {
+        println!("• env:");
+        for assignment in service.env_assignments() {
+            println!("    {assignment}");
+        }
+    }
+}
+
+/// Print the environment `up` would pass to the server, ready for `eval` or `source`.
+pub fn handle_env(service_type: ServiceType) -> Result<(), AppError> {
     let cfg = load_config()?;
-    let service = service_for_up(&cfg, service_type);
-    handle_service_up(service, &cfg)
+    for line in service_for_up(&cfg, service_type).env_exports() {
+        println!("{line}");
+    }
+    Ok(())
+}
+
+/// Flags that adjust how `fusion <service> down` behaves.
+#[derive(Debug, Clone, Default)]
+pub struct DownOptions {
+    /// SIGKILL instead of asking the server to exit.
+    pub force: bool,
+    /// Print a single JSON outcome object instead of text.
+    pub json: bool,
+    /// Kill processes matched by command signature without asking first.
+    pub yes: bool,
 }
 
+/// Stop the service without prompting, for callers that already decided to force a stop.
 pub fn handle_down(service_type: ServiceType, force: bool) -> Result<(), AppError> {
-    println!("🛑 Stopping {}...", service_label(service_type));
+    handle_down_with_options(service_type, &DownOptions { force, yes: true, ..Default::default() })
+}
+
+pub fn handle_down_with_options(
+    service_type: ServiceType,
+    options: &DownOptions,
+) -> Result<(), AppError> {
+    if !options.json {
+        println!("🛑 Stopping {}...", service_label(service_type));
+    }
     let cfg = load_config()?;
     let service = service_for_runtime(&cfg, service_type)?;
-    handle_service_down(service, force)
+    handle_service_down(service, options)
 }
 
 pub fn handle_ps_single(service_type: ServiceType) -> Result<(), AppError> {
@@ -37,163 +158,817 @@ pub fn handle_ps_single(service_type: ServiceType) -> Result<(), AppError> {
     handle_service_ps(service)
 }
 
-pub fn handle_logs_single(service_type: ServiceType) -> Result<(), AppError> {
+/// Which log lines `log` and `logs` print.
+#[derive(Debug, Clone)]
+pub struct LogOptions {
+    /// Every line stamped within this window, instead of a fixed number of lines.
+    pub since: Option<Duration>,
+    /// How many lines to print when `since` is not given.
+    pub lines: usize,
+    /// Print the first `lines` lines instead of the last.
+    pub head: bool,
+    /// Keep only lines containing this text; applied before `lines` is counted.
+    pub grep: Option<String>,
+}
+
+impl Default for LogOptions {
+    fn default() -> Self {
+        Self { since: None, lines: LOG_TAIL_LINES, head: false, grep: None }
+    }
+}
+
+/// Show the log location and the lines selected by `options` (by default the last 15).
+pub fn handle_logs_single(service_type: ServiceType, options: &LogOptions) -> Result<(), AppError> {
     println!("📜 {} log location:", service_label(service_type));
     let cfg = load_config()?;
     let service = service_for_runtime(&cfg, service_type)?;
-    handle_service_logs(service)
+    handle_service_logs(service, options)
+}
+
+/// Service state accepted by `fusion ps --filter`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusFilter {
+    Running,
+    Stopped,
+}
+
+impl StatusFilter {
+    fn matches(self, outcome: &StatusOutcome) -> bool {
+        match self {
+            StatusFilter::Running => matches!(outcome, StatusOutcome::Running { .. }),
+            StatusFilter::Stopped => matches!(outcome, StatusOutcome::NotRunning),
+        }
+    }
+}
+
+impl FromStr for StatusFilter {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "running" => Ok(StatusFilter::Running),
+            "stopped" => Ok(StatusFilter::Stopped),
+            other => Err(format!("unknown state '{other}' (expected running or stopped)")),
+        }
+    }
 }
 
 pub fn handle_ps() -> Result<(), AppError> {
+    handle_ps_filtered(None, 1)
+}
+
+/// List services in the `filter` state, querying up to `count` of them at once.
+pub fn handle_ps_filtered(filter: Option<StatusFilter>, count: usize) -> Result<(), AppError> {
     println!("ℹ️  Status for LLM runtimes:");
+    for (service, outcome) in service_statuses_concurrently(filter, count)? {
+        print_service_status(&service, outcome);
+    }
+    Ok(())
+}
+
+/// `fusion ps --porcelain`: one tab-separated `name state pid host port` line per service.
+pub fn handle_ps_porcelain(filter: Option<StatusFilter>, count: usize) -> Result<(), AppError> {
+    print!("{}", render_porcelain(&service_statuses_concurrently(filter, count)?));
+    Ok(())
+}
+
+/// Render statuses in the porcelain format. The column order is stable across releases;
+/// `state` is `running` or `stopped`, and `pid` is `-` for a stopped service.
+pub fn render_porcelain(statuses: &[(ManagedService, StatusOutcome)]) -> String {
+    statuses
+        .iter()
+        .map(|(service, outcome)| {
+            let (state, pid) = match outcome {
+                StatusOutcome::Running { pid } => ("running", pid.to_string()),
+                StatusOutcome::NotRunning => ("stopped", "-".to_string()),
+            };
+            format!("{}\t{state}\t{pid}\t{}\t{}\n", service.name, service.host, service.port)
+        })
+        .collect()
+}
+
+/// `fusion status`: the `ps` listing, or with `short` a single `name:up|down` line.
+pub fn handle_status(short: bool) -> Result<(), AppError> {
+    if !short {
+        return handle_ps();
+    }
+    println!("{}", render_short_status(&service_statuses(None)?));
+    Ok(())
+}
+
+/// One space-separated `name:up` or `name:down` token per service, for prompts and status bars.
+pub fn render_short_status(statuses: &[(ManagedService, StatusOutcome)]) -> String {
+    statuses
+        .iter()
+        .map(|(service, outcome)| match outcome {
+            StatusOutcome::Running { .. } => format!("{}:up", service.name),
+            StatusOutcome::NotRunning => format!("{}:down", service.name),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Status of every configured service, keeping only those in the `filter` state when given.
+pub fn service_statuses(
+    filter: Option<StatusFilter>,
+) -> Result<Vec<(ManagedService, StatusOutcome)>, AppError> {
+    service_statuses_concurrently(filter, 1)
+}
+
+/// Like [`service_statuses`], querying up to `count` services at once; the order is unchanged.
+pub fn service_statuses_concurrently(
+    filter: Option<StatusFilter>,
+    count: usize,
+) -> Result<Vec<(ManagedService, StatusOutcome)>, AppError> {
     let cfg = load_config()?;
-    for service in services::default_services(&cfg)? {
-        handle_service_ps(service)?;
+    let services = services::default_services(&cfg)?;
+    let outcomes = process::status_services(&services, count)?;
+    Ok(services
+        .into_iter()
+        .zip(outcomes)
+        .filter(|(_, outcome)| filter.is_none_or(|filter| filter.matches(outcome)))
+        .collect())
+}
+
+/// Clear the screen and reprint the global status every `interval_secs` until interrupted.
+pub fn handle_ps_watch(
+    interval_secs: u64,
+    filter: Option<StatusFilter>,
+    count: usize,
+) -> Result<(), AppError> {
+    let interval = Duration::from_secs(interval_secs.max(1));
+    loop {
+        style::clear_screen();
+        handle_ps_filtered(filter, count)?;
+        println!("\nRefreshing every {}s. Press Ctrl-C to exit.", interval.as_secs());
+        thread::sleep(interval);
     }
+}
+
+pub fn handle_metrics() -> Result<(), AppError> {
+    let cfg = load_config()?;
+    let services = services::default_services(&cfg)?;
+    print!("{}", metrics::render_prometheus(&services));
     Ok(())
 }
 
 pub fn handle_logs() -> Result<(), AppError> {
+    handle_logs_all(&LogOptions::default())
+}
+
+/// Print every service's log location and selected lines one after another.
+pub fn handle_logs_all(options: &LogOptions) -> Result<(), AppError> {
     println!("Log files:");
     let cfg = load_config()?;
     for service in services::default_services(&cfg)? {
-        handle_service_logs(service)?;
+        handle_service_logs(service, options)?;
     }
     println!("Use 'tail -f <log>' to follow output.");
     Ok(())
 }
 
+/// Ask before SIGKILLing processes found only by their command line, unless `yes` was given.
+/// Without a terminal to ask on, the matches are listed and nothing is killed.
+fn confirm_force_kill(service: &ManagedService, matches: &[SignatureMatch], yes: bool) -> bool {
+    if yes {
+        return true;
+    }
+    let stdin = io::stdin();
+    let mut stderr = io::stderr();
+    if !stdin.is_terminal() {
+        let _ = list_force_kill_matches(service, matches, &mut stderr);
+        eprintln!("Pass --yes to kill them without a prompt.");
+        return false;
+    }
+    prompt_force_kill(service, matches, &mut stdin.lock(), &mut stderr).unwrap_or(false)
+}
+
+fn prompt_force_kill(
+    service: &ManagedService,
+    matches: &[SignatureMatch],
+    input: &mut impl BufRead,
+    out: &mut impl Write,
+) -> io::Result<bool> {
+    list_force_kill_matches(service, matches, out)?;
+    write!(out, "Kill {} with SIGKILL? [y/N] ", plural(matches.len(), "process", "processes"))?;
+    out.flush()?;
+    let mut answer = String::new();
+    input.read_line(&mut answer)?;
+    Ok(matches!(answer.trim().to_ascii_lowercase().as_str(), "y" | "yes"))
+}
+
+fn list_force_kill_matches(
+    service: &ManagedService,
+    matches: &[SignatureMatch],
+    out: &mut impl Write,
+) -> io::Result<()> {
+    writeln!(out, "These processes match the {} command:", service.name)?;
+    for found in matches {
+        writeln!(out, "    {:>7}  {}", found.pid, found.command)?;
+    }
+    Ok(())
+}
+
+fn plural(count: usize, one: &str, many: &str) -> String {
+    format!("{count} {}", if count == 1 { one } else { many })
+}
+
 fn model_name_for_service<'a>(service: &ManagedService, cfg: &'a Config) -> &'a str {
-    if service.name == "ollama" {
-        cfg.ollama_server.model.as_str()
-    } else {
-        cfg.mlx_server.model.as_str()
+    match service.service_type {
+        ServiceType::Ollama => cfg.ollama_server.model.as_str(),
+        ServiceType::Mlx => cfg.mlx_server.model.as_str(),
     }
 }
 
-fn handle_service_up(service: ManagedService, cfg: &Config) -> Result<(), AppError> {
+fn endpoint(service: &ManagedService) -> String {
+    config::format_host_port(&service.host, service.port)
+}
+
+fn handle_service_up(
+    service: ManagedService,
+    cfg: &Config,
+    timeout_secs: u64,
+    probe: ReadinessProbe,
+    quiet: bool,
+    attach: bool,
+) -> Result<LifecycleReport, AppError> {
     let model_name = model_name_for_service(&service, cfg);
+    // Start from the current end so an appended log only echoes this launch's output.
+    let mut followers = Vec::new();
+    if attach {
+        followers.push(LogFollower::from_end(service.log_path()?));
+        followers.push(LogFollower::from_end(service.stderr_log_path()?));
+    }
+    let per_poll_timeout_secs = (timeout_secs / 10).max(2);
+    let ready = |_pid: i32| {
+        health::check_readiness(&service, model_name, probe, per_poll_timeout_secs).is_ok()
+    };
+    let ready: Option<&dyn Fn(i32) -> bool> = (timeout_secs > 0).then_some(&ready);
+    let say = |line: String| {
+        if !quiet {
+            println!("{line}");
+        }
+    };
 
-    match process::start_service(&service)? {
-        StartOutcome::Started { pid } => {
-            println!("• Process spawned with PID {}. Loading model...", pid);
-            wait_until_ready(&service, pid, model_name)?;
-            println!("✅ {} is ready on {}:{}", service.name, service.host, service.port);
+    let report = match process::start_service_with_ready_signal(&service, ready)? {
+        StartOutcome::Started { pid, .. } if timeout_secs == 0 => {
+            process::ensure_alive(&service, pid)?;
+            say(format!(
+                "✅ {} {} with PID {pid} on {} (not waiting for readiness)",
+                service.name,
+                style::green("started"),
+                endpoint(&service)
+            ));
+            LifecycleReport::start(service.name, pid, false)
+        }
+        StartOutcome::AlreadyRunning { pid } if timeout_secs == 0 => {
+            say(format!("• {} already running (pid {}).", service.name, pid));
+            LifecycleReport::start(service.name, pid, true)
+        }
+        StartOutcome::Started { pid, ready: true } => {
+            echo_followed_logs(&mut followers);
+            say(format!(
+                "✅ {} is {} on {} (PID {pid})",
+                service.name,
+                style::green("ready"),
+                endpoint(&service)
+            ));
+            LifecycleReport::start(service.name, pid, false)
+        }
+        StartOutcome::Started { pid, ready: false } => {
+            say(format!("• Process spawned with PID {}. Loading model...", pid));
+            let outcome = wait_until_ready(
+                &service,
+                pid,
+                model_name,
+                probe,
+                timeout_secs,
+                quiet,
+                &mut followers,
+            )?;
+            if outcome == WaitOutcome::Stopped {
+                eprintln!("{}", interrupted_guidance(&service, pid));
+                return Ok(LifecycleReport::start(service.name, pid, false));
+            }
+            say(format!(
+                "✅ {} is {} on {}",
+                service.name,
+                style::green("ready"),
+                endpoint(&service)
+            ));
+            LifecycleReport::start(service.name, pid, false)
         }
         StartOutcome::AlreadyRunning { pid } => {
-            println!("• {} already running (pid {}). Checking health...", service.name, pid);
-            wait_until_ready(&service, pid, model_name)?;
-            println!("✅ {} is ready.", service.name);
+            say(format!("• {} already running (pid {}). Checking health...", service.name, pid));
+            let outcome = wait_until_ready(
+                &service,
+                pid,
+                model_name,
+                probe,
+                timeout_secs,
+                quiet,
+                &mut followers,
+            )?;
+            if outcome == WaitOutcome::Stopped {
+                eprintln!("{}", interrupted_guidance(&service, pid));
+                return Ok(LifecycleReport::start(service.name, pid, true));
+            }
+            say(format!("✅ {} is {}.", service.name, style::green("ready")));
+            LifecycleReport::start(service.name, pid, true)
         }
+        StartOutcome::PortInUse { .. } => return Err(port_in_use_error(&service)),
+    };
+    Ok(report)
+}
+
+/// Fire-and-forget start for scripts: the PID is the only output.
+fn spawn_without_waiting(service: &ManagedService, json: bool) -> Result<(), AppError> {
+    let (pid, already_running) = match process::start_service(service)? {
+        StartOutcome::Started { pid, .. } => (pid, false),
+        StartOutcome::AlreadyRunning { pid } => (pid, true),
+        StartOutcome::PortInUse { .. } => return Err(port_in_use_error(service)),
+    };
+    if json {
+        LifecycleReport::start(service.name, pid, already_running).print();
+    } else {
+        println!("{pid}");
     }
     Ok(())
 }
 
-fn handle_service_down(service: ManagedService, force: bool) -> Result<(), AppError> {
-    match process::stop_service(&service, force)? {
+/// Machine-readable result of `up`/`down`, printed instead of the text lines with `--json`.
+#[derive(Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+enum LifecycleReport {
+    Start { service: &'static str, pid: i32, already_running: bool },
+    Stop { service: &'static str, pid: Option<i32>, was_running: bool, forced: bool, count: usize },
+}
+
+impl LifecycleReport {
+    fn start(service: &'static str, pid: i32, already_running: bool) -> Self {
+        Self::Start { service, pid, already_running }
+    }
+
+    fn stop(service: &'static str, outcome: StopOutcome) -> Self {
+        let (pid, count, forced) = match outcome {
+            StopOutcome::Stopped { pid, forced } => (Some(pid), 1, forced),
+            StopOutcome::TerminatedByName { count, forced } => (None, count, forced),
+            StopOutcome::ForceTerminated { pid, count } => (pid, count, true),
+            StopOutcome::NotRunning => (None, 0, false),
+        };
+        Self::Stop { service, pid, was_running: count > 0, forced, count }
+    }
+
+    fn print(&self) {
+        println!("{}", serde_json::to_string(self).expect("lifecycle report serializes"));
+    }
+}
+
+fn port_in_use_error(service: &ManagedService) -> AppError {
+    AppError::process_error(
+        service.name,
+        format!("{} is already in use by another process; not starting.", endpoint(service)),
+    )
+}
+
+fn handle_service_down(service: ManagedService, options: &DownOptions) -> Result<(), AppError> {
+    let mut confirm =
+        |matches: &[SignatureMatch]| confirm_force_kill(&service, matches, options.yes);
+    let outcome = process::stop_service_confirmed(&service, options.force, &mut confirm)?;
+    if options.json {
+        LifecycleReport::stop(service.name, outcome).print();
+        return Ok(());
+    }
+    match outcome {
         StopOutcome::Stopped { forced, .. } => {
             if forced {
-                println!("• {} force-stopped on {}:{}", service.name, service.host, service.port);
+                println!(
+                    "• {} {} on {}",
+                    service.name,
+                    style::red("force-stopped"),
+                    endpoint(&service)
+                );
             } else {
-                println!("• {} stopped on {}:{}", service.name, service.host, service.port);
+                println!("• {} {} on {}", service.name, style::red("stopped"), endpoint(&service));
             }
         }
         StopOutcome::TerminatedByName { count, forced } => {
             let action = if forced { "killed with SIGKILL" } else { "signaled" };
             println!(
-                "• {} stopped by signature on {}:{} ({} process{} {action})",
+                "• {} {} by signature on {} ({} process{} {action})",
                 service.name,
-                service.host,
-                service.port,
+                style::red("stopped"),
+                endpoint(&service),
                 count,
                 if count == 1 { "" } else { "es" }
             );
         }
+        StopOutcome::ForceTerminated { pid, count } => {
+            let target = match pid {
+                Some(pid) => format!("pid {pid}"),
+                None => format!("{count} process{}", if count == 1 { "" } else { "es" }),
+            };
+            println!(
+                "• {} {} on {} ({target}; no graceful stop on Windows)",
+                service.name,
+                style::red("force-terminated"),
+                endpoint(&service)
+            );
+        }
         StopOutcome::NotRunning => {
-            println!("• {} is not running on {}:{}", service.name, service.host, service.port);
+            println!(
+                "• {} is {} on {}",
+                service.name,
+                style::red("not running"),
+                endpoint(&service)
+            );
         }
     }
     Ok(())
 }
 
 fn handle_service_ps(service: ManagedService) -> Result<(), AppError> {
-    match process::status_service(&service)? {
+    let outcome = process::status_service(&service)?;
+    print_service_status(&service, outcome);
+    Ok(())
+}
+
+fn print_service_status(service: &ManagedService, outcome: StatusOutcome) {
+    match outcome {
         StatusOutcome::Running { pid } => {
+            let uptime = process::uptime(service, pid)
+                .map(|uptime| format!(", up {}", format_uptime(uptime)))
+                .unwrap_or_default();
             println!(
-                "• {}: running on {}:{} (pid {pid})",
-                service.name, service.host, service.port
+                "• {}: {} on {} (pid {pid}{uptime})",
+                service.name,
+                style::green("running"),
+                endpoint(service)
             );
         }
         StatusOutcome::NotRunning => {
-            println!("• {}: not running on {}:{}", service.name, service.host, service.port);
+            println!("• {}: {} on {}", service.name, style::red("not running"), endpoint(service));
         }
     }
-    Ok(())
 }
 
-fn handle_service_logs(service: ManagedService) -> Result<(), AppError> {
+/// Compact uptime with the two most significant units, e.g. `45s`, `12m5s`, `3h12m`, `2d4h`.
+fn format_uptime(uptime: Duration) -> String {
+    let secs = uptime.as_secs();
+    let (days, hours, minutes) = (secs / 86_400, secs / 3600 % 24, secs / 60 % 60);
+    match secs {
+        0..60 => format!("{secs}s"),
+        60..3600 => format!("{minutes}m{}s", secs % 60),
+        3600..86_400 => format!("{hours}h{minutes}m"),
+        _ => format!("{days}d{hours}h"),
+    }
+}
+
+fn handle_service_logs(service: ManagedService, options: &LogOptions) -> Result<(), AppError> {
     paths::ensure_pid_dir()?;
     let log_path = service.log_path()?;
-    println!("• {}: {}", service.name, log_path.display());
-    match fs::read_to_string(&log_path) {
-        Ok(contents) => {
-            for line in tail_lines(&contents, LOG_TAIL_LINES) {
-                println!("    {line}");
-            }
-        }
-        Err(err) if err.kind() == io::ErrorKind::NotFound => {
-            println!("    (log file not found)");
-        }
-        Err(err) => return Err(err.into()),
+    let stderr_path = service.stderr_log_path()?;
+    println!("• {}: {} (stderr: {})", service.name, log_path.display(), stderr_path.display());
+    // A plain tail only needs the end of each file, however large the logs have grown.
+    let plain_tail = options.since.is_none() && !options.head && options.grep.is_none();
+    let read = |path: &Path| {
+        if plain_tail { read_optional_tail(path, options.lines) } else { read_optional(path) }
+    };
+    let (stdout, stderr) = (read(&log_path)?, read(&stderr_path)?);
+    if stdout.is_none() && stderr.is_none() {
+        println!("    (log file not found)");
+        return Ok(());
+    }
+    // Show both streams as one log, interleaved by their line stamps.
+    let contents = log_stamp::merge_by_time(
+        stdout.as_deref().unwrap_or_default(),
+        stderr.as_deref().unwrap_or_default(),
+    )
+    .join("\n");
+    for line in select_log_lines(&contents, options) {
+        println!("    {line}");
     }
     Ok(())
 }
 
-fn tail_lines(contents: &str, count: usize) -> impl Iterator<Item = String> {
-    let mut lines = VecDeque::with_capacity(count);
-    for line in contents.lines() {
-        if lines.len() == count {
-            lines.pop_front();
-        }
-        lines.push_back(line.to_string());
+fn read_optional_tail(path: &Path, lines: usize) -> Result<Option<String>, AppError> {
+    match process::tail_file(path, lines) {
+        Ok(lines) => Ok(Some(lines.join("\n"))),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err.into()),
     }
-    lines.into_iter()
 }
 
-fn wait_until_ready(service: &ManagedService, pid: i32, model_name: &str) -> Result<(), AppError> {
-    let start = Instant::now();
-    let timeout_secs = startup_timeout_secs();
-    let timeout = Duration::from_secs(timeout_secs);
-    // Derive per-poll timeout from overall startup timeout, with a minimum of 2 seconds
-    let per_poll_timeout_secs = (timeout_secs / 10).max(2);
+fn read_optional(path: &Path) -> Result<Option<String>, AppError> {
+    match fs::read_to_string(path) {
+        Ok(contents) => Ok(Some(contents)),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err.into()),
+    }
+}
 
-    println!("⏳ Waiting for {} to become ready (Timeout: {}s)...", service.name, timeout_secs);
+/// Apply `since`, then `grep`, then keep the first or last `lines` of what remains; a `since`
+/// window is printed in full.
+fn select_log_lines<'a>(contents: &'a str, options: &LogOptions) -> Vec<&'a str> {
+    let lines = match options.since {
+        Some(window) => log_stamp::lines_since(contents, log_stamp::cutoff_for(window)),
+        None => contents.lines().collect(),
+    };
+    let mut lines: Vec<&str> = match &options.grep {
+        Some(pattern) => lines.into_iter().filter(|line| line.contains(pattern.as_str())).collect(),
+        None => lines,
+    };
+    if options.since.is_none() {
+        if options.head {
+            lines.truncate(options.lines);
+        } else {
+            lines.drain(..lines.len().saturating_sub(options.lines));
+        }
+    }
+    lines
+}
 
-    while start.elapsed() < timeout {
-        if !process::is_process_alive(service, pid) {
-            let log_tail = process::read_stderr_tail(service, 10).unwrap_or_default();
-            return Err(AppError::process_error(
-                service.name,
-                format!("Process died unexpectedly during startup.\nCheck logs:\n{}", log_tail),
-            ));
+/// [`health::wait_for_readiness_with`] plus the CLI's progress hints, log echo, and Ctrl-C check.
+fn wait_until_ready(
+    service: &ManagedService,
+    pid: i32,
+    model_name: &str,
+    probe: ReadinessProbe,
+    timeout_secs: u64,
+    quiet: bool,
+    followers: &mut [LogFollower],
+) -> Result<WaitOutcome, AppError> {
+    let _scope = InterruptScope::enter();
+    if !quiet {
+        println!("⏳ Waiting for {} to become ready (Timeout: {}s)...", service.name, timeout_secs);
+    }
+    let mut last_hint: Option<String> = None;
+
+    health::wait_for_readiness_with(service, pid, model_name, probe, timeout_secs, |poll| {
+        match poll {
+            ReadinessPoll::Probing => {
+                if INTERRUPTED.load(Ordering::SeqCst) {
+                    return ControlFlow::Break(());
+                }
+                echo_followed_logs(followers);
+            }
+            ReadinessPoll::NotReady(err) => {
+                let hint = readiness_hint(service, err);
+                if !quiet && last_hint.as_ref() != Some(&hint) {
+                    println!("   {hint}");
+                    last_hint = Some(hint);
+                }
+            }
+            ReadinessPoll::Ready => echo_followed_logs(followers),
         }
+        ControlFlow::Continue(())
+    })
+}
+
+/// Set by the Ctrl-C handler while a startup wait is in progress.
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+static WAITING: AtomicBool = AtomicBool::new(false);
+static INSTALL_HANDLER: Once = Once::new();
+
+/// Marks a startup wait so Ctrl-C ends the wait instead of the process. Outside a wait the
+/// handler exits with the conventional status 130, as an unhandled SIGINT would.
+struct InterruptScope;
 
-        match health::check_inference_readiness(service, model_name, per_poll_timeout_secs) {
-            Ok(_) => return Ok(()),
-            Err(_) => {
-                thread::sleep(Duration::from_millis(POLLING_INTERVAL_MS));
+impl InterruptScope {
+    fn enter() -> Self {
+        INSTALL_HANDLER.call_once(|| {
+            let installed = ctrlc::set_handler(|| {
+                if WAITING.load(Ordering::SeqCst) {
+                    INTERRUPTED.store(true, Ordering::SeqCst);
+                } else {
+                    std::process::exit(130);
+                }
+            });
+            if let Err(err) = installed {
+                tracing::debug!(error = %err, "could not install Ctrl-C handler");
             }
+        });
+        WAITING.store(true, Ordering::SeqCst);
+        InterruptScope
+    }
+}
+
+impl Drop for InterruptScope {
+    fn drop(&mut self) {
+        WAITING.store(false, Ordering::SeqCst);
+        INTERRUPTED.store(false, Ordering::SeqCst);
+    }
+}
+
+fn interrupted_guidance(service: &ManagedService, pid: i32) -> String {
+    format!(
+        "Interrupted: {} is still starting in the background (PID {pid}); run `fusion {} down` to stop it.",
+        service.name, service.name
+    )
+}
+
+fn echo_followed_logs(followers: &mut [LogFollower]) {
+    for follower in followers {
+        follower.echo_new_lines();
+    }
+}
+
+/// Echoes lines appended to a log file since it was opened, for `up --attach`.
+struct LogFollower {
+    path: PathBuf,
+    offset: u64,
+    partial: String,
+}
+
+impl LogFollower {
+    fn from_end(path: PathBuf) -> Self {
+        let offset = fs::metadata(&path).map(|meta| meta.len()).unwrap_or(0);
+        Self { path, offset, partial: String::new() }
+    }
+
+    /// Best effort: a log that cannot be read must not abort the startup wait.
+    fn echo_new_lines(&mut self) {
+        if let Err(err) = self.write_new_lines(&mut io::stdout().lock()) {
+            tracing::debug!(error = %err, "could not follow log");
         }
     }
 
-    Err(AppError::process_error(service.name, "Timed out waiting for service to be ready."))
+    /// Write complete lines added since the last call; a trailing partial line waits for more.
+    fn write_new_lines(&mut self, out: &mut impl Write) -> io::Result<()> {
+        let mut file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+            Err(err) => return Err(err),
+        };
+        if file.metadata()?.len() < self.offset {
+            // Truncated or replaced since the last read.
+            self.offset = 0;
+        }
+        file.seek(SeekFrom::Start(self.offset))?;
+        let mut appended = Vec::new();
+        file.read_to_end(&mut appended)?;
+        self.offset += appended.len() as u64;
+        self.partial.push_str(&String::from_utf8_lossy(&appended));
+        while let Some(newline) = self.partial.find('\n') {
+            let line: String = self.partial.drain(..=newline).collect();
+            writeln!(out, "    {}", line.trim_end())?;
+        }
+        out.flush()
+    }
 }
 
-fn startup_timeout_secs() -> u64 {
-    if let Ok(value) = std::env::var("FUSION_STARTUP_TIMEOUT_SECS")
-        && let Ok(parsed) = value.parse::<u64>()
-    {
-        return parsed;
+/// Progress hint distinguishing a server that is not listening from one still loading its model.
+fn readiness_hint(service: &ManagedService, err: &ReadinessError) -> String {
+    match err {
+        ReadinessError::Unreachable(_) => {
+            format!("• Waiting for port {} to accept connections...", endpoint(service))
+        }
+        ReadinessError::NotReady { status } => {
+            format!("• Server is listening; loading model (HTTP {status})...")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uptime_is_formatted_with_two_units() {
+        let secs = Duration::from_secs;
+        assert_eq!(format_uptime(secs(0)), "0s");
+        assert_eq!(format_uptime(secs(45)), "45s");
+        assert_eq!(format_uptime(secs(60)), "1m0s");
+        assert_eq!(format_uptime(secs(12 * 60 + 5)), "12m5s");
+        assert_eq!(format_uptime(secs(3 * 3600 + 12 * 60 + 59)), "3h12m");
+        assert_eq!(format_uptime(secs(2 * 86_400 + 4 * 3600 + 30)), "2d4h");
+    }
+
+    #[test]
+    fn log_selection_supports_grep_head_and_tail() {
+        let log: String = (1..=20)
+            .map(|n| if n % 5 == 0 { format!("line {n} ERROR\n") } else { format!("line {n}\n") })
+            .collect();
+
+        let tail = select_log_lines(&log, &LogOptions::default());
+        assert_eq!(tail.len(), 15);
+        assert_eq!(tail[0], "line 6");
+        assert_eq!(tail[14], "line 20 ERROR");
+
+        let grep = LogOptions { grep: Some("ERROR".into()), ..LogOptions::default() };
+        assert_eq!(
+            select_log_lines(&log, &grep),
+            ["line 5 ERROR", "line 10 ERROR", "line 15 ERROR", "line 20 ERROR"]
+        );
+
+        let head = LogOptions { head: true, lines: 3, ..LogOptions::default() };
+        assert_eq!(select_log_lines(&log, &head), ["line 1", "line 2", "line 3"]);
+
+        let both = LogOptions { lines: 2, ..grep.clone() };
+        assert_eq!(select_log_lines(&log, &both), ["line 15 ERROR", "line 20 ERROR"]);
+        let both_head = LogOptions { head: true, ..both };
+        assert_eq!(select_log_lines(&log, &both_head), ["line 5 ERROR", "line 10 ERROR"]);
+    }
+
+    #[test]
+    fn log_follower_echoes_only_new_complete_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("mlx.log");
+        fs::write(&path, "previous launch\n").unwrap();
+        let mut follower = LogFollower::from_end(path.clone());
+
+        let mut log = fs::OpenOptions::new().append(true).open(&path).unwrap();
+        log.write_all(b"Fetching 6 files\nLoading wei").unwrap();
+        let mut out = Vec::new();
+        follower.write_new_lines(&mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "    Fetching 6 files\n");
+
+        log.write_all(b"ghts\nStarting httpd\n").unwrap();
+        let mut out = Vec::new();
+        follower.write_new_lines(&mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "    Loading weights\n    Starting httpd\n");
+    }
+
+    #[test]
+    fn interrupted_wait_returns_without_failing_and_resets_the_flag() {
+        let service = services::create_ollama_service(&Config::default().ollama_server);
+        let pid = std::process::id() as i32;
+        INTERRUPTED.store(true, Ordering::SeqCst);
+
+        let outcome =
+            wait_until_ready(&service, pid, "m", ReadinessProbe::Inference, 30, true, &mut [])
+                .expect("an interrupted wait is not an error");
+        assert_eq!(outcome, WaitOutcome::Stopped);
+        assert!(!INTERRUPTED.load(Ordering::SeqCst));
+        assert!(!WAITING.load(Ordering::SeqCst));
+        assert!(
+            interrupted_guidance(&service, pid).contains("run `fusion ollama down` to stop it")
+        );
+    }
+
+    #[test]
+    fn force_kill_prompt_lists_matches_and_defaults_to_no() {
+        let service = services::create_ollama_service(&Config::default().ollama_server);
+        let matches = [
+            SignatureMatch { pid: 41, command: "ollama serve".into() },
+            SignatureMatch { pid: 42, command: "/usr/local/bin/ollama serve".into() },
+        ];
+
+        let mut out = Vec::new();
+        let killed = prompt_force_kill(&service, &matches, &mut "\n".as_bytes(), &mut out).unwrap();
+        assert!(!killed);
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("     41  ollama serve"), "{out}");
+        assert!(out.contains("     42  /usr/local/bin/ollama serve"), "{out}");
+        assert!(out.contains("Kill 2 processes with SIGKILL? [y/N]"), "{out}");
+
+        let mut sink = Vec::new();
+        assert!(prompt_force_kill(&service, &matches, &mut "Y\n".as_bytes(), &mut sink).unwrap());
+        assert!(confirm_force_kill(&service, &matches, true));
+    }
+
+    fn json(report: &LifecycleReport) -> serde_json::Value {
+        serde_json::from_str(&serde_json::to_string(report).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn start_reports_serialize_fresh_and_existing_processes() {
+        assert_eq!(
+            json(&LifecycleReport::start("ollama", 1234, false)),
+            serde_json::json!({
+                "action": "start",
+                "service": "ollama",
+                "pid": 1234,
+                "already_running": false
+            })
+        );
+        let existing = json(&LifecycleReport::start("mlx", 42, true));
+        assert_eq!(existing["action"], "start");
+        assert_eq!(existing["already_running"], true);
+    }
+
+    #[test]
+    fn stop_reports_cover_every_outcome() {
+        let stopped =
+            json(&LifecycleReport::stop("ollama", StopOutcome::Stopped { pid: 7, forced: false }));
+        assert_eq!(
+            stopped,
+            serde_json::json!({
+                "action": "stop",
+                "service": "ollama",
+                "pid": 7,
+                "was_running": true,
+                "forced": false,
+                "count": 1
+            })
+        );
+
+        let idle = json(&LifecycleReport::stop("mlx", StopOutcome::NotRunning));
+        assert_eq!(idle["was_running"], false);
+        assert_eq!(idle["pid"], serde_json::Value::Null);
+
+        let by_name =
+            LifecycleReport::stop("mlx", StopOutcome::TerminatedByName { count: 2, forced: true });
+        assert_eq!(json(&by_name)["count"], 2);
     }
-    DEFAULT_STARTUP_TIMEOUT_SECS
 }
@@ -1,10 +1,25 @@
 mod config;
+mod doctor;
 mod health;
+mod integration;
 mod lifecycle;
+mod models;
+mod proxy;
 mod shared;
+mod style;
+mod version;
 
-pub use config::{ServiceConfigCommand, handle_config};
-pub use health::handle_health_single;
+pub use config::{ConfigFormat, ServiceConfigCommand, handle_config};
+pub use doctor::handle_doctor;
+pub use health::{handle_health_single, handle_ping};
+pub use integration::{handle_launchd, handle_systemd};
 pub use lifecycle::{
-    handle_down, handle_logs, handle_logs_single, handle_ps, handle_ps_single, handle_up,
+    DownOptions, LogOptions, StatusFilter, UpOptions, handle_down, handle_down_with_options,
+    handle_env, handle_logs, handle_logs_all, handle_logs_single, handle_metrics, handle_ps,
+    handle_ps_filtered, handle_ps_porcelain, handle_ps_single, handle_ps_watch, handle_status,
+    handle_up, handle_up_with_options, render_porcelain, render_short_status, service_statuses,
+    service_statuses_concurrently,
 };
+pub use models::{handle_api, handle_models, handle_pull, handle_show};
+pub use proxy::handle_proxy;
+pub use version::handle_version;
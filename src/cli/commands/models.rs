@@ -0,0 +1,135 @@
+use super::shared::{load_config, service_for_runtime};
+use crate::cli::{ServiceType, service_label};
+use crate::core::api;
+use crate::error::AppError;
+
+const MODELS_TIMEOUT_SECS: u64 = 10;
+const API_TIMEOUT_SECS: u64 = 60;
+
+pub fn handle_pull(service_type: ServiceType, model: &str) -> Result<(), AppError> {
+    if service_type != ServiceType::Ollama {
+        return Err(AppError::config_error(
+            "pull is only supported for Ollama; MLX downloads models on first use",
+        ));
+    }
+    let cfg = load_config()?;
+    let service = service_for_runtime(&cfg, service_type)?;
+
+    println!("⬇️  Pulling {model}...");
+    let mut last_line = String::new();
+    api::pull_model(&service, model, |progress| {
+        let line = match progress.percent() {
+            Some(percent) => format!("   {}: {percent}%", progress.status),
+            None => format!("   {}", progress.status),
+        };
+        // Ollama repeats lines as bytes arrive; only print when the visible text changes.
+        if line != last_line {
+            println!("{line}");
+            last_line = line;
+        }
+    })?;
+    println!("✅ Pulled {model}");
+    Ok(())
+}
+
+/// Print the metadata of `model`, or of the configured model when omitted.
+pub fn handle_show(service_type: ServiceType, model: Option<&str>) -> Result<(), AppError> {
+    if service_type != ServiceType::Ollama {
+        return Err(AppError::config_error("show is only supported for Ollama"));
+    }
+    let cfg = load_config()?;
+    let service = service_for_runtime(&cfg, service_type)?;
+    let model = model.unwrap_or(&cfg.ollama_server.model);
+    let info = api::show_model(&service, model, MODELS_TIMEOUT_SECS)?;
+
+    println!("📄 {model}:");
+    let summary = &info.details;
+    let fields = [
+        ("family", summary.family.clone()),
+        ("format", summary.format.clone()),
+        ("quantization", summary.quantization_level.clone()),
+        ("parameters", parameter_line(summary.parameter_size.as_deref(), info.parameter_count())),
+        ("license", info.license.as_deref().and_then(|text| text.lines().next()).map(Into::into)),
+        (
+            "template",
+            info.template.as_deref().map(|text| format!("{} lines", text.lines().count())),
+        ),
+    ];
+    for (label, value) in fields {
+        if let Some(value) = value {
+            println!("• {label}: {value}");
+        }
+    }
+    if let Some(parameters) = info.parameters.as_deref().filter(|text| !text.trim().is_empty()) {
+        println!("• modelfile parameters:");
+        for line in parameters.lines() {
+            println!("    {}", line.trim());
+        }
+    }
+    Ok(())
+}
+
+fn parameter_line(size: Option<&str>, count: Option<u64>) -> Option<String> {
+    match (size, count) {
+        (Some(size), Some(count)) => Some(format!("{size} ({count})")),
+        (Some(size), None) => Some(size.to_string()),
+        (None, Some(count)) => Some(count.to_string()),
+        (None, None) => None,
+    }
+}
+
+pub fn handle_models(service_type: ServiceType) -> Result<(), AppError> {
+    let cfg = load_config()?;
+    let service = service_for_runtime(&cfg, service_type)?;
+    let models = api::list_models(&service, MODELS_TIMEOUT_SECS)?;
+
+    println!("📦 {} models:", service_label(service_type));
+    if models.is_empty() {
+        println!("• (none)");
+    }
+    for model in models {
+        match model.size {
+            Some(size) => println!("• {} ({})", model.name, format_size(size)),
+            None => println!("• {}", model.name),
+        }
+    }
+    Ok(())
+}
+
+/// Send an arbitrary request to the backend and print the (pretty-printed, if JSON) response.
+pub fn handle_api(
+    service_type: ServiceType,
+    method: &str,
+    path: &str,
+    body: Option<&str>,
+) -> Result<(), AppError> {
+    let cfg = load_config()?;
+    let service = service_for_runtime(&cfg, service_type)?;
+    let response = api::send_raw(&service, method, path, body, API_TIMEOUT_SECS)?;
+    match serde_json::from_str::<serde_json::Value>(&response.body) {
+        Ok(json) => {
+            let pretty = serde_json::to_string_pretty(&json).unwrap_or(response.body);
+            println!("{pretty}");
+        }
+        Err(_) if response.body.is_empty() => {}
+        Err(_) => println!("{}", response.body),
+    }
+    if !(200..300).contains(&response.status) {
+        return Err(AppError::http_error(
+            service.name,
+            format!("Service responded with status: {}", response.status),
+        ));
+    }
+    Ok(())
+}
+
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1000.0 && unit < UNITS.len() - 1 {
+        value /= 1000.0;
+        unit += 1;
+    }
+    if unit == 0 { format!("{bytes} B") } else { format!("{value:.1} {}", UNITS[unit]) }
+}
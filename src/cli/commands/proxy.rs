@@ -0,0 +1,33 @@
+use super::shared::load_config;
+use crate::core::config;
+use crate::core::proxy::{self, ProxyBackend};
+use crate::core::services;
+use crate::error::AppError;
+use std::net::TcpListener;
+
+pub fn handle_proxy(host: &str, port: u16) -> Result<(), AppError> {
+    let cfg = load_config()?;
+    let backends = vec![
+        ProxyBackend {
+            service: services::load_ollama_service(&cfg.ollama_server)?,
+            model: cfg.ollama_server.model.clone(),
+        },
+        ProxyBackend {
+            service: services::load_mlx_service(&cfg.mlx_server)?,
+            model: cfg.mlx_server.model.clone(),
+        },
+    ];
+
+    let address = config::format_host_port(host, port);
+    let listener = TcpListener::bind(&address)?;
+    println!("🔀 Proxying http://{address}/v1/chat/completions");
+    for backend in &backends {
+        println!(
+            "   {} → {} ({})",
+            backend.model,
+            backend.service.name,
+            backend.service.base_url()
+        );
+    }
+    proxy::serve(&listener, &backends)
+}
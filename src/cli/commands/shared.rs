@@ -8,10 +8,7 @@ pub(super) fn load_config() -> Result<Config, AppError> {
 }
 
 pub(super) fn service_for_up(cfg: &Config, service_type: ServiceType) -> ManagedService {
-    match service_type {
-        ServiceType::Ollama => services::create_ollama_service(&cfg.ollama_server),
-        ServiceType::Mlx => services::create_mlx_service(&cfg.mlx_server),
-    }
+    services::service_for_type(cfg, service_type)
 }
 
 pub(super) fn service_for_runtime(
@@ -0,0 +1,57 @@
+//! Terminal colouring helpers that honour the `NO_COLOR` convention and non-TTY output.
+
+use std::env;
+use std::ffi::OsString;
+use std::io::{self, IsTerminal};
+
+const GREEN: &str = "32";
+const RED: &str = "31";
+
+/// Render `text` in green when stdout supports colour.
+pub(super) fn green(text: &str) -> String {
+    paint(text, GREEN, color_enabled())
+}
+
+/// Render `text` in red when stdout supports colour.
+pub(super) fn red(text: &str) -> String {
+    paint(text, RED, color_enabled())
+}
+
+/// Clear the terminal and move the cursor home; a no-op when stdout is not a TTY.
+pub(super) fn clear_screen() {
+    if io::stdout().is_terminal() {
+        print!("\x1b[2J\x1b[H");
+    }
+}
+
+fn color_enabled() -> bool {
+    color_enabled_for(io::stdout().is_terminal(), env::var_os("NO_COLOR"))
+}
+
+fn color_enabled_for(is_terminal: bool, no_color: Option<OsString>) -> bool {
+    // Per no-color.org, only a non-empty NO_COLOR value disables colour.
+    is_terminal && no_color.is_none_or(|value| value.is_empty())
+}
+
+fn paint(text: &str, code: &str, enabled: bool) -> String {
+    if enabled { format!("\x1b[{code}m{text}\x1b[0m") } else { text.to_string() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn colour_requires_terminal_and_no_no_color() {
+        assert!(color_enabled_for(true, None));
+        assert!(color_enabled_for(true, Some(OsString::new())));
+        assert!(!color_enabled_for(true, Some(OsString::from("1"))));
+        assert!(!color_enabled_for(false, None));
+    }
+
+    #[test]
+    fn paint_is_plain_when_disabled() {
+        assert_eq!(paint("running", GREEN, false), "running");
+        assert_eq!(paint("running", GREEN, true), "\x1b[32mrunning\x1b[0m");
+    }
+}
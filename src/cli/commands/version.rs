@@ -0,0 +1,37 @@
+use crate::core::paths;
+use crate::error::AppError;
+use serde::Serialize;
+
+/// Build and runtime details worth attaching to bug reports.
+#[derive(Debug, Serialize)]
+struct VersionInfo {
+    version: &'static str,
+    git_commit: &'static str,
+    rustc: &'static str,
+    config_path: Option<String>,
+}
+
+impl VersionInfo {
+    fn collect() -> Self {
+        Self {
+            version: env!("CARGO_PKG_VERSION"),
+            git_commit: env!("FUSION_GIT_COMMIT"),
+            rustc: env!("FUSION_RUSTC_VERSION"),
+            config_path: paths::user_config_file().ok().map(|path| path.display().to_string()),
+        }
+    }
+}
+
+pub fn handle_version(json: bool) -> Result<(), AppError> {
+    let info = VersionInfo::collect();
+    if json {
+        let rendered = serde_json::to_string_pretty(&info)
+            .map_err(|err| AppError::config_error(format!("Failed to render version: {err}")))?;
+        println!("{rendered}");
+        return Ok(());
+    }
+    println!("fusion {} ({})", info.version, info.git_commit);
+    println!("• rustc: {}", info.rustc);
+    println!("• config: {}", info.config_path.as_deref().unwrap_or("(unresolved)"));
+    Ok(())
+}
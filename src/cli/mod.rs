@@ -1,14 +1,15 @@
 mod commands;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum ServiceType {
-    Ollama,
-    Mlx,
-}
+pub use crate::core::services::ServiceType;
 
 pub use commands::{
-    ServiceConfigCommand, handle_config, handle_down, handle_health_single, handle_logs,
-    handle_logs_single, handle_ps, handle_ps_single, handle_up,
+    ConfigFormat, DownOptions, LogOptions, ServiceConfigCommand, StatusFilter, UpOptions,
+    handle_api, handle_config, handle_doctor, handle_down, handle_down_with_options, handle_env,
+    handle_health_single, handle_launchd, handle_logs, handle_logs_all, handle_logs_single,
+    handle_metrics, handle_models, handle_ping, handle_proxy, handle_ps, handle_ps_filtered,
+    handle_ps_porcelain, handle_ps_single, handle_ps_watch, handle_pull, handle_show,
+    handle_status, handle_systemd, handle_up, handle_up_with_options, handle_version,
+    render_porcelain, render_short_status, service_statuses, service_statuses_concurrently,
 };
 
 pub(crate) fn service_label(service_type: ServiceType) -> &'static str {
@@ -0,0 +1,363 @@
+//! HTTP queries against a managed runtime's model APIs, plus a raw passthrough for endpoints
+//! fusion has no dedicated command for.
+
+use crate::core::services::{ManagedService, ServiceType};
+use crate::core::{http, http_log};
+use crate::error::AppError;
+use reqwest::Method;
+use serde::Deserialize;
+use std::io::{BufRead, BufReader};
+use std::time::Duration;
+
+/// A model advertised by a backend; `size` is only reported by Ollama.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelInfo {
+    pub name: String,
+    pub size: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct OllamaTags {
+    #[serde(default)]
+    models: Vec<OllamaTag>,
+}
+
+#[derive(Deserialize)]
+struct OllamaTag {
+    name: String,
+    #[serde(default)]
+    size: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct OpenAiModels {
+    #[serde(default)]
+    data: Vec<OpenAiModel>,
+}
+
+#[derive(Deserialize)]
+struct OpenAiModel {
+    id: String,
+}
+
+/// Model metadata from Ollama's `/api/show`; every field is optional across Ollama versions.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct ModelDetails {
+    /// Modelfile `PARAMETER` lines, e.g. `temperature 0.6`, one per line.
+    #[serde(default)]
+    pub parameters: Option<String>,
+    #[serde(default)]
+    pub template: Option<String>,
+    #[serde(default)]
+    pub license: Option<String>,
+    #[serde(default)]
+    pub details: ModelSummary,
+    #[serde(default)]
+    model_info: serde_json::Map<String, serde_json::Value>,
+}
+
+/// The `details` block of `/api/show`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct ModelSummary {
+    #[serde(default)]
+    pub format: Option<String>,
+    #[serde(default)]
+    pub family: Option<String>,
+    /// Rounded size such as `3.2B`.
+    #[serde(default)]
+    pub parameter_size: Option<String>,
+    #[serde(default)]
+    pub quantization_level: Option<String>,
+}
+
+impl ModelDetails {
+    /// Exact parameter count from `model_info`, when the server reports it.
+    pub fn parameter_count(&self) -> Option<u64> {
+        self.model_info.get("general.parameter_count").and_then(serde_json::Value::as_u64)
+    }
+}
+
+/// One streamed status line from Ollama's `/api/pull`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct PullProgress {
+    #[serde(default)]
+    pub status: String,
+    #[serde(default)]
+    pub completed: Option<u64>,
+    #[serde(default)]
+    pub total: Option<u64>,
+    #[serde(default)]
+    error: Option<String>,
+}
+
+impl PullProgress {
+    /// Download progress of the current layer, when the line reports byte counts.
+    pub fn percent(&self) -> Option<u64> {
+        match (self.completed, self.total) {
+            (Some(completed), Some(total)) if total > 0 => Some(completed.min(total) * 100 / total),
+            _ => None,
+        }
+    }
+}
+
+/// Pull `model` through Ollama's `/api/pull`, reporting each streamed status line.
+///
+/// Downloads can take arbitrarily long, so only the connection attempt is time-limited.
+/// A pull is not replayed once sent; only a failure to connect is retried.
+#[tracing::instrument(skip_all, fields(service = service.name, model = model))]
+pub fn pull_model(
+    service: &ManagedService,
+    model: &str,
+    mut on_progress: impl FnMut(&PullProgress),
+) -> Result<(), AppError> {
+    let client = http::client_for(service, None)?;
+
+    let url = service.url("/api/pull");
+    let payload = serde_json::json!({ "model": model, "stream": true });
+    let response =
+        http::send_with_retry(|| client.post(&url).json(&payload).send()).map_err(|e| {
+            AppError::http_source(
+                service.name,
+                "Connection failed (is it running? try `fusion ollama up`)",
+                e,
+            )
+        })?;
+    http_log::record(
+        service.name,
+        "POST",
+        &url,
+        Some(&payload),
+        response.status().as_u16(),
+        "(streamed progress)",
+    );
+    if !response.status().is_success() {
+        return Err(AppError::http_error(
+            service.name,
+            format!("Service responded with status: {}", response.status()),
+        ));
+    }
+
+    for line in BufReader::new(response).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let progress: PullProgress = serde_json::from_str(&line).map_err(|e| {
+            AppError::http_error(service.name, format!("Failed to parse pull progress: {e}"))
+        })?;
+        if let Some(error) = &progress.error {
+            return Err(AppError::http_error(service.name, format!("Pull failed: {error}")));
+        }
+        on_progress(&progress);
+    }
+    Ok(())
+}
+
+/// Fetch metadata for `model` through Ollama's `/api/show`.
+#[tracing::instrument(skip_all, fields(service = service.name, model = model))]
+pub fn show_model(
+    service: &ManagedService,
+    model: &str,
+    timeout_secs: u64,
+) -> Result<ModelDetails, AppError> {
+    let client = http::client_for(service, Some(Duration::from_secs(timeout_secs)))?;
+    let url = service.url("/api/show");
+    let payload = serde_json::json!({ "name": model });
+    let response = http::send_with_retry(|| client.post(&url).json(&payload).send())
+        .map_err(|e| AppError::http_source(service.name, "Connection failed", e))?;
+    let status = response.status();
+    let body = response
+        .text()
+        .map_err(|e| AppError::http_source(service.name, "Failed to read response", e))?;
+    http_log::record(service.name, "POST", &url, Some(&payload), status.as_u16(), &body);
+    if !status.is_success() {
+        return Err(AppError::http_error(
+            service.name,
+            format!("Service responded with status: {status}"),
+        ));
+    }
+    serde_json::from_str(&body).map_err(|e| {
+        AppError::http_error(service.name, format!("Failed to parse JSON response: {e}"))
+    })
+}
+
+/// List the models a service can serve: Ollama's `/api/tags` or the OpenAI `/v1/models` list.
+#[tracing::instrument(skip_all, fields(service = service.name))]
+pub fn list_models(
+    service: &ManagedService,
+    timeout_secs: u64,
+) -> Result<Vec<ModelInfo>, AppError> {
+    let client = http::client_for(service, Some(Duration::from_secs(timeout_secs)))?;
+
+    let path = match service.service_type {
+        ServiceType::Ollama => "/api/tags",
+        ServiceType::Mlx => "/v1/models",
+    };
+    let url = service.url(path);
+
+    let response = http::send_idempotent_with_retry(|| client.get(&url).send())
+        .map_err(|e| AppError::http_source(service.name, "Connection failed", e))?;
+    let status = response.status();
+    let body = response
+        .text()
+        .map_err(|e| AppError::http_source(service.name, "Failed to read response", e))?;
+    http_log::record(service.name, "GET", &url, None, status.as_u16(), &body);
+
+    if !status.is_success() {
+        return Err(AppError::http_error(
+            service.name,
+            format!("Service responded with status: {status}"),
+        ));
+    }
+    parse_models(service, &body)
+}
+
+/// Methods accepted by [`send_raw`].
+const RAW_METHODS: [&str; 6] = ["GET", "POST", "PUT", "PATCH", "DELETE", "HEAD"];
+
+/// Status and body of a passthrough request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawResponse {
+    pub status: u16,
+    pub body: String,
+}
+
+/// Send `method path` to the service with an optional JSON `body`, returning whatever it answers.
+#[tracing::instrument(skip_all, fields(service = service.name, method = method, path = path))]
+pub fn send_raw(
+    service: &ManagedService,
+    method: &str,
+    path: &str,
+    body: Option<&str>,
+    timeout_secs: u64,
+) -> Result<RawResponse, AppError> {
+    let method = method.to_ascii_uppercase();
+    if !RAW_METHODS.contains(&method.as_str()) {
+        return Err(AppError::config_error(format!(
+            "unsupported method '{method}' (expected one of {})",
+            RAW_METHODS.join(", ")
+        )));
+    }
+    let method = Method::from_bytes(method.as_bytes())
+        .map_err(|e| AppError::config_error(format!("invalid method: {e}")))?;
+    if !path.starts_with('/') {
+        return Err(AppError::config_error(format!("path must start with '/', got '{path}'")));
+    }
+    let payload = body
+        .map(serde_json::from_str::<serde_json::Value>)
+        .transpose()
+        .map_err(|e| AppError::config_error(format!("--body is not valid JSON: {e}")))?;
+
+    let client = http::client_for(service, Some(Duration::from_secs(timeout_secs)))?;
+    let url = service.url(path);
+    let send = || {
+        let request = client.request(method.clone(), &url);
+        match &payload {
+            Some(payload) => request.json(payload).send(),
+            None => request.send(),
+        }
+    };
+    // Only replay a request that may have reached the service when the method is safe to repeat.
+    let response = if matches!(method, Method::GET | Method::HEAD) {
+        http::send_idempotent_with_retry(send)
+    } else {
+        http::send_with_retry(send)
+    }
+    .map_err(|e| AppError::http_source(service.name, "Connection failed", e))?;
+    let status = response.status().as_u16();
+    let body = response
+        .text()
+        .map_err(|e| AppError::http_source(service.name, "Failed to read response", e))?;
+    http_log::record(service.name, method.as_str(), &url, payload.as_ref(), status, &body);
+    Ok(RawResponse { status, body })
+}
+
+fn parse_models(service: &ManagedService, body: &str) -> Result<Vec<ModelInfo>, AppError> {
+    let parse_error = |e: serde_json::Error| {
+        AppError::http_error(service.name, format!("Failed to parse JSON response: {e}"))
+    };
+    match service.service_type {
+        ServiceType::Ollama => {
+            let tags: OllamaTags = serde_json::from_str(body).map_err(parse_error)?;
+            Ok(tags
+                .models
+                .into_iter()
+                .map(|tag| ModelInfo { name: tag.name, size: tag.size })
+                .collect())
+        }
+        ServiceType::Mlx => {
+            let models: OpenAiModels = serde_json::from_str(body).map_err(parse_error)?;
+            Ok(models
+                .data
+                .into_iter()
+                .map(|model| ModelInfo { name: model.id, size: None })
+                .collect())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::{MlxServerConfig, OllamaServerConfig};
+    use crate::core::services;
+    use std::net::TcpListener;
+    use std::thread;
+
+    #[test]
+    fn parse_models_reads_both_response_shapes() {
+        let ollama_service = services::create_ollama_service(&OllamaServerConfig::default());
+        let mlx_service = services::create_mlx_service(&MlxServerConfig::default());
+        let ollama = r#"{"models":[{"name":"llama3.2:3b","size":2019393189}]}"#;
+        assert_eq!(
+            parse_models(&ollama_service, ollama).unwrap(),
+            vec![ModelInfo { name: "llama3.2:3b".into(), size: Some(2019393189) }]
+        );
+
+        let openai = r#"{"object":"list","data":[{"id":"mlx-community/a"},{"id":"b"}]}"#;
+        let names: Vec<_> = parse_models(&mlx_service, openai)
+            .unwrap()
+            .into_iter()
+            .map(|model| model.name)
+            .collect();
+        assert_eq!(names, ["mlx-community/a", "b"]);
+    }
+
+    #[test]
+    fn pull_progress_percent_requires_byte_counts() {
+        let line = r#"{"status":"pulling abc","completed":50,"total":200}"#;
+        let progress: PullProgress = serde_json::from_str(line).unwrap();
+        assert_eq!(progress.percent(), Some(25));
+
+        let progress: PullProgress = serde_json::from_str(r#"{"status":"success"}"#).unwrap();
+        assert_eq!(progress.percent(), None);
+    }
+
+    #[test]
+    fn send_raw_does_not_replay_a_post_that_reached_the_service() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let cfg = MlxServerConfig {
+            port: listener.local_addr().unwrap().port(),
+            ..MlxServerConfig::default()
+        };
+        let service = services::create_mlx_service(&cfg);
+
+        let stub = thread::spawn(move || {
+            // Read the request, then drop the connection without answering.
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap() > 0 && line != "\r\n" {
+                line.clear();
+            }
+            drop(reader);
+            listener
+        });
+
+        let result = send_raw(&service, "POST", "/v1/completions", Some("{}"), 5);
+        assert!(result.is_err(), "dropped POST should fail: {result:?}");
+        let listener = stub.join().unwrap();
+        listener.set_nonblocking(true).unwrap();
+        assert!(listener.accept().is_err(), "POST must not be sent a second time");
+    }
+}
@@ -1,3 +1,4 @@
+use crate::error::AppError;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 use toml::Value as TomlValue;
@@ -12,8 +13,23 @@ pub struct MlxServerConfig {
     pub host: String,
     #[serde(default = "default_mlx_port")]
     pub port: u16,
+    /// URL scheme used to reach the server: `http`, or `https` behind a TLS proxy.
+    #[serde(default = "super::default_scheme")]
+    pub scheme: String,
     #[serde(default = "default_mlx_model")]
     pub model: String,
+    /// Additional arguments appended to the server command line.
+    #[serde(default)]
+    pub extra_args: Vec<String>,
+    /// HTTP headers sent with every request to the server; values may reference `${VAR}`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+    /// Endpoint polled for readiness instead of the built-in chat completion probe.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ready_path: Option<String>,
+    /// Method for `ready_path`: `POST` sends a one-token chat request, `GET` expects any 2xx.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ready_method: Option<super::ReadyMethod>,
     #[serde(default)]
     #[serde(flatten)]
     pub extra: BTreeMap<String, TomlValue>,
@@ -24,12 +40,36 @@ impl Default for MlxServerConfig {
         Self {
             host: default_mlx_host(),
             port: default_mlx_port(),
+            scheme: super::default_scheme(),
             model: default_mlx_model(),
+            extra_args: Vec::new(),
+            headers: BTreeMap::new(),
+            ready_path: None,
+            ready_method: None,
             extra: BTreeMap::new(),
         }
     }
 }
 
+impl MlxServerConfig {
+    /// Reject model values `mlx_lm.server` would only fail on cryptically after spawning: it needs a
+    /// Hugging Face repo id (`org/name`) or a local path.
+    pub fn validate_model(&self) -> Result<(), AppError> {
+        let model = self.model.trim();
+        if model.is_empty() {
+            return Err(AppError::config_error(
+                "mlx_server.model is empty; set it with `fusion config set mlx_server.model <org/name>`",
+            ));
+        }
+        if model.chars().any(char::is_whitespace) {
+            return Err(AppError::config_error(format!(
+                "mlx_server.model '{model}' contains whitespace; expected a Hugging Face repo id or local path"
+            )));
+        }
+        Ok(())
+    }
+}
+
 fn default_mlx_host() -> String {
     DEFAULT_MLX_HOST.to_string()
 }
@@ -1,21 +1,45 @@
 use crate::core::paths;
 use crate::error::AppError;
 use serde::{Deserialize, Serialize};
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fs;
 use std::io::Write;
 use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
 use toml::Value as TomlValue;
 use toml_edit::{DocumentMut, Item, Table, Value as TomlEditValue};
 
 mod mlx;
 mod ollama;
+mod schema;
 
 pub use mlx::*;
 pub use ollama::*;
+pub use schema::*;
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub const DEFAULT_SCHEME: &str = "http";
+/// Schema version written by this build; files without a `version` key predate versioning (0).
+pub const CONFIG_VERSION: u32 = 1;
+
+/// HTTP method used by a configured readiness endpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum ReadyMethod {
+    #[serde(alias = "get")]
+    Get,
+    #[serde(alias = "post")]
+    Post,
+}
+
+fn default_scheme() -> String {
+    DEFAULT_SCHEME.to_string()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
+    #[serde(default)]
+    pub version: u32,
     #[serde(default)]
     pub ollama_server: OllamaServerConfig,
     #[serde(default)]
@@ -25,10 +49,148 @@ pub struct Config {
     pub extra: BTreeMap<String, TomlValue>,
 }
 
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            version: CONFIG_VERSION,
+            ollama_server: OllamaServerConfig::default(),
+            mlx_server: MlxServerConfig::default(),
+            extra: BTreeMap::new(),
+        }
+    }
+}
+
+impl Config {
+    /// Reject settings that deserialise but cannot start a server, such as port 0 or an empty model.
+    pub fn validate(&self) -> Result<(), AppError> {
+        let ollama = &self.ollama_server;
+        validate_server("ollama_server", &ollama.host, ollama.port, &ollama.scheme, &ollama.model)?;
+        let mlx = &self.mlx_server;
+        validate_server("mlx_server", &mlx.host, mlx.port, &mlx.scheme, &mlx.model)?;
+        validate_ready_path("ollama_server", ollama.ready_path.as_deref())?;
+        validate_ready_path("mlx_server", mlx.ready_path.as_deref())?;
+        self.check_port_conflicts()
+    }
+
+    /// Reject two services bound to the same port on overlapping hosts, since the second `up`
+    /// would fail with an unhelpful bind error.
+    pub fn check_port_conflicts(&self) -> Result<(), AppError> {
+        let binds = [
+            ("ollama_server", normalise_host(&self.ollama_server.host), self.ollama_server.port),
+            ("mlx_server", normalise_host(&self.mlx_server.host), self.mlx_server.port),
+        ];
+        for (index, (section, host, port)) in binds.iter().enumerate() {
+            for (other, other_host, other_port) in &binds[index + 1..] {
+                let wildcard = |host: &str| matches!(host, "0.0.0.0" | "::");
+                let overlaps = host == other_host || wildcard(host) || wildcard(other_host);
+                if port == other_port && overlaps {
+                    return Err(AppError::config_error(format!(
+                        "{section} and {other} are both configured on port {port}; give each service its own port"
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Reject server sections where several `extra` keys set the same environment variable.
+    pub fn check_env_collisions(&self) -> Result<(), AppError> {
+        let sections = [
+            ("ollama_server", &self.ollama_server.extra, "OLLAMA_"),
+            ("mlx_server", &self.mlx_server.extra, "MLX_"),
+        ];
+        for (section, extra, prefix) in sections {
+            if let Some((variable, keys)) = env_key_collisions(extra, prefix).into_iter().next() {
+                return Err(AppError::config_error(format!(
+                    "{section} keys {} all set {variable}; keep only one",
+                    keys.join(", ")
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn validate_server(
+    section: &str,
+    host: &str,
+    port: u16,
+    scheme: &str,
+    model: &str,
+) -> Result<(), AppError> {
+    if normalise_host(host).is_empty() {
+        return Err(AppError::config_error(format!("{section}.host must not be empty")));
+    }
+    if port == 0 {
+        return Err(AppError::config_error(format!("{section}.port must not be 0")));
+    }
+    if !matches!(scheme, "http" | "https") {
+        return Err(AppError::config_error(format!(
+            "{section}.scheme must be \"http\" or \"https\", got \"{scheme}\""
+        )));
+    }
+    if model.trim().is_empty() {
+        return Err(AppError::config_error(format!("{section}.model must not be empty")));
+    }
+    Ok(())
+}
+
+fn validate_ready_path(section: &str, path: Option<&str>) -> Result<(), AppError> {
+    match path {
+        Some(path) if !path.starts_with('/') => Err(AppError::config_error(format!(
+            "{section}.ready_path must start with '/', got \"{path}\""
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// When set, `extra` keys that collide on one environment variable fail `up` (`--strict-env`).
+static STRICT_ENV: AtomicBool = AtomicBool::new(false);
+
+pub fn set_strict_env(strict: bool) {
+    STRICT_ENV.store(strict, Ordering::Relaxed);
+}
+
+pub fn is_strict_env() -> bool {
+    STRICT_ENV.load(Ordering::Relaxed)
+}
+
+/// When set, config safety warnings (loose file permissions, a public bind host) are errors.
+static STRICT: AtomicBool = AtomicBool::new(false);
+static PERMISSION_WARNING_SHOWN: AtomicBool = AtomicBool::new(false);
+
+/// Fail on config safety warnings instead of printing them (`fusion --strict`).
+pub fn set_strict(strict: bool) {
+    STRICT.store(strict, Ordering::Relaxed);
+}
+
+/// Whether `--strict` promotes config safety warnings to errors.
+pub fn is_strict() -> bool {
+    STRICT.load(Ordering::Relaxed)
+}
+
+/// Return a warning when `host` listens on every interface (`0.0.0.0` or `::`) and the bind was
+/// not acknowledged with `allow_public`; with `strict` it is an error.
+pub fn check_public_bind(
+    section: &str,
+    host: &str,
+    allow_public: bool,
+    strict: bool,
+) -> Result<Option<String>, AppError> {
+    let host = normalise_host(host);
+    if allow_public || !matches!(host, "0.0.0.0" | "::") {
+        return Ok(None);
+    }
+    let message = format!(
+        "{section}.host is {host}, so the model server accepts unauthenticated requests from \
+         any machine that can reach this one; bind 127.0.0.1 instead, or pass \
+         --allow-public-bind if that is intended"
+    );
+    if strict { Err(AppError::config_error(message)) } else { Ok(Some(message)) }
+}
+
 pub fn load_config() -> Result<Config, AppError> {
-    ensure_config_exists()?;
-    let path = paths::user_config_file()?;
-    let contents = fs::read_to_string(&path)?;
+    let contents = read_config_file()?;
     let config: Config = toml::from_str(&contents)
         .map_err(|err| AppError::config_error(format!("Failed to parse config: {err}")))?;
     Ok(config)
@@ -40,9 +202,7 @@ pub fn save_config(config: &Config) -> Result<(), AppError> {
 }
 
 pub fn load_config_document() -> Result<DocumentMut, AppError> {
-    ensure_config_exists()?;
-    let path = paths::user_config_file()?;
-    let contents = fs::read_to_string(&path)?;
+    let contents = read_config_file()?;
     contents
         .parse::<DocumentMut>()
         .map_err(|err| AppError::config_error(format!("Failed to parse config: {err}")))
@@ -62,13 +222,118 @@ fn write_config_to_path(path: &Path, config: &Config) -> Result<(), AppError> {
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent)?;
     }
-    let mut file = fs::File::create(path)?;
+    let mut file = create_private(path)?;
     let contents = toml::to_string_pretty(config)
         .map_err(|err| AppError::config_error(format!("Failed to serialise config: {err}")))?;
     file.write_all(contents.as_bytes())?;
     Ok(())
 }
 
+/// Validate `key`, convert `raw` (inferring its type unless `kind` is given), and persist it.
+pub fn set_config_value(key: &str, raw: &str, kind: Option<ValueKind>) -> Result<(), AppError> {
+    validate_config_key(key)?;
+    if is_keep_alive_key(key) {
+        parse_duration_spec(raw)?;
+    }
+    let value = match kind {
+        Some(kind) => typed_toml_edit_value(raw, kind)?,
+        None => infer_toml_edit_value(raw),
+    };
+    let mut document = load_config_document()?;
+    let key_path: Vec<&str> = key.split('.').collect();
+    set_document_value(&mut document, &key_path, value)?;
+    // Reject values the typed config cannot deserialise (e.g. a string port) before saving.
+    toml::from_str::<Config>(&document.to_string())
+        .map_err(|err| AppError::config_error(format!("Invalid value for '{key}': {err}")))?;
+    save_config_document(&document)
+}
+
+/// Validate `contents` as a complete config and install it verbatim as the active config file.
+///
+/// Returns `false` when the active file already has identical contents. A differing file is
+/// only replaced with `force`.
+pub fn import_config(contents: &str, force: bool) -> Result<bool, AppError> {
+    let config: Config = toml::from_str(contents)
+        .map_err(|err| AppError::config_error(format!("Failed to parse config: {err}")))?;
+    config.validate()?;
+
+    let path = paths::user_config_file()?;
+    match fs::read_to_string(&path) {
+        Ok(existing) if existing == contents => return Ok(false),
+        Ok(_) if !force => {
+            return Err(AppError::config_error(format!(
+                "{} already exists with different contents; pass --force to replace it",
+                path.display()
+            )));
+        }
+        Ok(_) => {}
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+        Err(err) => return Err(err.into()),
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = create_private(&path)?;
+    // A replaced file keeps its old mode, so tighten it before the new contents land.
+    restrict_permissions(&path)?;
+    file.write_all(contents.as_bytes())?;
+    Ok(true)
+}
+
+/// Upgrade the active config file to [`CONFIG_VERSION`], filling in defaults for missing fields
+/// while keeping every user value and unknown key.
+///
+/// Returns the previous version when the file was rewritten, or `None` if it was already current.
+pub fn migrate_config() -> Result<Option<u32>, AppError> {
+    let mut document = load_config_document()?;
+    let before = document.to_string();
+    let mut config: Config = toml::from_str(&before)
+        .map_err(|err| AppError::config_error(format!("Failed to parse config: {err}")))?;
+    let previous = config.version;
+    if previous > CONFIG_VERSION {
+        return Err(AppError::config_error(format!(
+            "config version {previous} is newer than this fusion supports ({CONFIG_VERSION})"
+        )));
+    }
+    // Version 0 -> 1 only added fields, so serde defaults cover it; renames would go here.
+    config.version = CONFIG_VERSION;
+
+    // Edit the existing document rather than reserialising, so comments and layout survive.
+    let migrated = toml::to_string_pretty(&config)
+        .map_err(|err| AppError::config_error(format!("Failed to serialise config: {err}")))?
+        .parse::<DocumentMut>()
+        .map_err(|err| AppError::config_error(format!("Failed to parse config: {err}")))?;
+    insert_missing(document.as_table_mut(), migrated.as_table());
+    document["version"] = toml_edit::value(i64::from(CONFIG_VERSION));
+    if document.to_string() == before {
+        return Ok(None);
+    }
+    save_config_document(&document)?;
+    Ok(Some(previous))
+}
+
+/// Insert every key of `source` that `target` lacks, recursing into tables both have, and leave
+/// existing entries as written.
+fn insert_missing(target: &mut Table, source: &Table) {
+    for (key, item) in source.iter() {
+        match (target.get_mut(key), item) {
+            (Some(Item::Table(existing)), Item::Table(defaults)) => {
+                insert_missing(existing, defaults)
+            }
+            (Some(_), _) => {}
+            (None, _) => {
+                target.insert(key, item.clone());
+            }
+        }
+    }
+}
+
+/// Raw contents of the active config file, created with defaults if missing.
+pub fn export_config() -> Result<String, AppError> {
+    read_config_file()
+}
+
 pub fn ensure_config_exists() -> Result<(), AppError> {
     let path = paths::user_config_file()?;
     if path.exists() {
@@ -77,15 +342,306 @@ pub fn ensure_config_exists() -> Result<(), AppError> {
 
     write_config_to_path(&path, &Config::default())
 }
+
+fn read_config_file() -> Result<String, AppError> {
+    ensure_config_exists()?;
+    let path = paths::user_config_file()?;
+    if let Some(warning) = check_config_permissions(&path, is_strict())?
+        && !PERMISSION_WARNING_SHOWN.swap(true, Ordering::Relaxed)
+    {
+        eprintln!("⚠️  {warning}");
+    }
+    Ok(fs::read_to_string(&path)?)
+}
+
+/// Return a warning if `path` is readable by group or others; with `strict` it is an error.
+#[cfg(unix)]
+pub fn check_config_permissions(path: &Path, strict: bool) -> Result<Option<String>, AppError> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mode = fs::metadata(path)?.permissions().mode() & 0o777;
+    if mode & 0o077 == 0 {
+        return Ok(None);
+    }
+    let message = format!(
+        "{} has mode {mode:04o}; run `chmod 600 {}` to keep it private",
+        path.display(),
+        path.display()
+    );
+    if strict { Err(AppError::config_error(message)) } else { Ok(Some(message)) }
+}
+
+#[cfg(not(unix))]
+pub fn check_config_permissions(_path: &Path, _strict: bool) -> Result<Option<String>, AppError> {
+    Ok(None)
+}
+
+/// Open `path` for writing, truncated; a file created here is private (`0600`) from the start.
+fn create_private(path: &Path) -> std::io::Result<fs::File> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options.open(path)
+}
+
+#[cfg(unix)]
+fn restrict_permissions(path: &Path) -> Result<(), AppError> {
+    use std::os::unix::fs::PermissionsExt;
+
+    fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn restrict_permissions(_path: &Path) -> Result<(), AppError> {
+    Ok(())
+}
+
+/// A leaf value in a TOML document addressed by its dotted key path.
+#[derive(Debug, Clone)]
+pub struct DocumentLeaf {
+    pub key: String,
+    pub value: TomlEditValue,
+}
+
+/// Serialise the default configuration into an editable TOML document.
+pub fn default_config_document() -> Result<DocumentMut, AppError> {
+    let contents = toml::to_string_pretty(&Config::default())
+        .map_err(|err| AppError::config_error(format!("Failed to serialise config: {err}")))?;
+    contents
+        .parse::<DocumentMut>()
+        .map_err(|err| AppError::config_error(format!("Failed to parse config: {err}")))
+}
+
+/// Recursively collect every leaf value in `document` in document order.
+pub fn document_leaves(document: &DocumentMut) -> Vec<DocumentLeaf> {
+    let mut leaves = Vec::new();
+    collect_table_leaves("", document.as_table(), &mut leaves);
+    leaves
+}
+
+fn collect_table_leaves(prefix: &str, table: &Table, leaves: &mut Vec<DocumentLeaf>) {
+    for (key, item) in table.iter() {
+        let path = if prefix.is_empty() { key.to_string() } else { format!("{prefix}.{key}") };
+        match item {
+            Item::Table(child) => collect_table_leaves(&path, child, leaves),
+            Item::Value(TomlEditValue::InlineTable(inline)) => {
+                collect_table_leaves(&path, &inline.clone().into_table(), leaves)
+            }
+            Item::Value(value) => leaves.push(DocumentLeaf { key: path, value: value.clone() }),
+            Item::ArrayOfTables(_) | Item::None => {}
+        }
+    }
+}
+
+/// Look up the value at a dotted key path, if present.
+pub fn document_value<'a>(document: &'a DocumentMut, key: &str) -> Option<&'a TomlEditValue> {
+    let mut item = document.as_item();
+    for segment in key.split('.') {
+        item = item.get(segment)?;
+    }
+    item.as_value()
+}
+
+/// Human-readable TOML type name for a value.
+pub fn value_type_name(value: &TomlEditValue) -> &'static str {
+    match value {
+        TomlEditValue::String(_) => "string",
+        TomlEditValue::Integer(_) => "integer",
+        TomlEditValue::Float(_) => "float",
+        TomlEditValue::Boolean(_) => "boolean",
+        TomlEditValue::Datetime(_) => "datetime",
+        TomlEditValue::Array(_) => "array",
+        TomlEditValue::InlineTable(_) => "table",
+    }
+}
+
+/// A single dotted key whose value differs between two configurations.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigDifference {
+    pub key: String,
+    pub default: Option<TomlValue>,
+    pub current: Option<TomlValue>,
+}
+
+/// Flatten a configuration into dotted leaf keys such as `ollama_server.port`.
+pub fn flatten_config(config: &Config) -> Result<BTreeMap<String, TomlValue>, AppError> {
+    let value = TomlValue::try_from(config)
+        .map_err(|err| AppError::config_error(format!("Failed to serialise config: {err}")))?;
+    let mut flat = BTreeMap::new();
+    flatten_value("", &value, &mut flat);
+    Ok(flat)
+}
+
+fn flatten_value(prefix: &str, value: &TomlValue, flat: &mut BTreeMap<String, TomlValue>) {
+    match value {
+        TomlValue::Table(table) => {
+            for (key, child) in table {
+                let path = if prefix.is_empty() { key.clone() } else { format!("{prefix}.{key}") };
+                flatten_value(&path, child, flat);
+            }
+        }
+        leaf => {
+            flat.insert(prefix.to_string(), leaf.clone());
+        }
+    }
+}
+
+/// List every key whose value in `current` differs from `defaults`, including `extra` entries.
+pub fn diff_configs(
+    defaults: &Config,
+    current: &Config,
+) -> Result<Vec<ConfigDifference>, AppError> {
+    let defaults = flatten_config(defaults)?;
+    let current = flatten_config(current)?;
+    let keys: BTreeSet<&String> = defaults.keys().chain(current.keys()).collect();
+    Ok(keys
+        .into_iter()
+        .filter_map(|key| {
+            let default = defaults.get(key);
+            let value = current.get(key);
+            (default != value).then(|| ConfigDifference {
+                key: key.clone(),
+                default: default.cloned(),
+                current: value.cloned(),
+            })
+        })
+        .collect())
+}
+
+/// Environment for a server from its `extra` keys, prefixing names that lack `prefix`. When two
+/// keys map to the same variable (`keep_alive` and `OLLAMA_KEEP_ALIVE`), the one already written
+/// as the variable name wins; see [`env_key_collisions`].
 pub fn server_env(extra: &BTreeMap<String, TomlValue>, prefix: &str) -> HashMap<String, String> {
     let mut env = HashMap::new();
+    let mut exact = HashSet::new();
     for (key, value) in extra {
         let normalized = normalise_env_key(key, prefix);
+        let is_exact = key.trim() == normalized;
+        if exact.contains(&normalized) || (env.contains_key(&normalized) && !is_exact) {
+            tracing::warn!(key = %key, env = %normalized, "ignoring duplicate environment key");
+            continue;
+        }
+        if is_exact {
+            exact.insert(normalized.clone());
+        }
         env.insert(normalized, toml_value_to_string(value));
     }
     env
 }
 
+/// Groups of `extra` keys that normalise to the same environment variable, as
+/// `(variable, keys)` in key order.
+pub fn env_key_collisions(
+    extra: &BTreeMap<String, TomlValue>,
+    prefix: &str,
+) -> Vec<(String, Vec<String>)> {
+    let mut groups: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for key in extra.keys() {
+        groups.entry(normalise_env_key(key, prefix)).or_default().push(key.clone());
+    }
+    groups.into_iter().filter(|(_, keys)| keys.len() > 1).collect()
+}
+
+/// Parse a `KEY=VALUE` assignment as given to `up --env`. The value may be empty or contain `=`.
+pub fn parse_env_assignment(raw: &str) -> Result<(String, String), String> {
+    match raw.split_once('=') {
+        Some((key, value)) if !key.trim().is_empty() => {
+            Ok((key.trim().to_string(), value.to_string()))
+        }
+        Some(_) => Err(format!("missing variable name in '{raw}'")),
+        None => Err(format!("expected KEY=VALUE, got '{raw}'")),
+    }
+}
+
+/// Replace each `${NAME}` in `value` with that environment variable, so secrets such as API keys
+/// can stay out of the config file. An unset variable is an error rather than an empty string.
+pub fn expand_env_vars(value: &str) -> Result<String, AppError> {
+    let mut expanded = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        expanded.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let end = after
+            .find('}')
+            .ok_or_else(|| AppError::config_error(format!("unterminated `${{` in '{value}'")))?;
+        let name = &after[..end];
+        let resolved = std::env::var(name).map_err(|_| {
+            AppError::config_error(format!("environment variable {name} is not set"))
+        })?;
+        expanded.push_str(&resolved);
+        rest = &after[end + 1..];
+    }
+    expanded.push_str(rest);
+    Ok(expanded)
+}
+
+/// Keep-alive setting in the forms accepted by `OLLAMA_KEEP_ALIVE` and per-request `keep_alive`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeepAlive {
+    /// A negative value keeps the model loaded indefinitely.
+    Forever,
+    /// `0` unloads the model as soon as a request finishes.
+    Unload,
+    For(Duration),
+}
+
+/// Parse a keep-alive value: bare seconds (`300`, `0`, `-1`) or unit sequences such as `10m`,
+/// `1h30m`, `45s`, or `500ms`.
+pub fn parse_duration_spec(raw: &str) -> Result<KeepAlive, AppError> {
+    let invalid = || {
+        AppError::config_error(format!(
+            "invalid keep-alive '{raw}': use seconds (e.g. 300, 0, -1) or units like 10m or 1h30m"
+        ))
+    };
+    let spec = raw.trim();
+    if let Ok(secs) = spec.parse::<i64>() {
+        return Ok(match secs {
+            ..0 => KeepAlive::Forever,
+            0 => KeepAlive::Unload,
+            secs => KeepAlive::For(Duration::from_secs(secs.unsigned_abs())),
+        });
+    }
+
+    let (negative, mut rest) = match spec.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, spec),
+    };
+    if rest.is_empty() {
+        return Err(invalid());
+    }
+    let mut total = Duration::ZERO;
+    while !rest.is_empty() {
+        let digits = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+        let value: u64 = rest[..digits].parse().map_err(|_| invalid())?;
+        rest = &rest[digits..];
+        let unit = rest.find(|c: char| c.is_ascii_digit()).unwrap_or(rest.len());
+        let part = match &rest[..unit] {
+            "h" => Duration::from_secs(value.saturating_mul(3_600)),
+            "m" => Duration::from_secs(value.saturating_mul(60)),
+            "s" => Duration::from_secs(value),
+            "ms" => Duration::from_millis(value),
+            _ => return Err(invalid()),
+        };
+        total = total.saturating_add(part);
+        rest = &rest[unit..];
+    }
+    Ok(match (negative, total.is_zero()) {
+        (true, false) => KeepAlive::Forever,
+        (_, true) => KeepAlive::Unload,
+        (false, false) => KeepAlive::For(total),
+    })
+}
+
+fn is_keep_alive_key(key: &str) -> bool {
+    key.strip_prefix("ollama_server.")
+        .is_some_and(|leaf| normalise_env_key(leaf, "OLLAMA_") == "OLLAMA_KEEP_ALIVE")
+}
+
 fn normalise_env_key(key: &str, prefix: &str) -> String {
     let upper = key.trim().to_uppercase();
     if upper.starts_with(prefix) { upper } else { format!("{prefix}{upper}") }
@@ -103,15 +659,77 @@ fn toml_value_to_string(value: &TomlValue) -> String {
 }
 
 pub fn format_host_port(host: &str, port: u16) -> String {
-    if host.contains(':') && !host.starts_with('[') && !host.ends_with(']') {
-        format!("[{host}]:{port}")
+    let host = normalise_host(host);
+    if host.contains(':') { format!("[{host}]:{port}") } else { format!("{host}:{port}") }
+}
+
+/// Strip surrounding whitespace and IPv6 brackets so hosts are stored in a single canonical form.
+pub fn normalise_host(host: &str) -> &str {
+    let trimmed = host.trim();
+    trimmed.strip_prefix('[').and_then(|rest| rest.strip_suffix(']')).unwrap_or(trimmed)
+}
+
+/// Parse a `host:port` pair, accepting bracketed IPv6 literals such as `[::1]:8080`.
+pub fn parse_host_port(value: &str) -> Option<(String, u16)> {
+    let value = value.trim();
+    let (host, port) = if let Some(rest) = value.strip_prefix('[') {
+        let (host, tail) = rest.split_once(']')?;
+        (host, tail.strip_prefix(':')?)
     } else {
-        format!("{host}:{port}")
+        let (host, port) = value.rsplit_once(':')?;
+        if host.contains(':') {
+            return None;
+        }
+        (host, port)
+    };
+    if host.is_empty() {
+        return None;
+    }
+    port.parse::<u16>().ok().map(|port| (host.to_string(), port))
+}
+
+fn parse_toml_array(raw: &str) -> Option<TomlEditValue> {
+    let document = format!("value = {raw}").parse::<DocumentMut>().ok()?;
+    document.get("value")?.as_value().filter(|value| value.is_array()).cloned()
+}
+
+/// Explicit value type requested for `config set`, bypassing inference.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueKind {
+    String,
+    Integer,
+    Float,
+    Boolean,
+}
+
+/// Convert `raw` to exactly the requested TOML type, failing if it does not parse.
+pub fn typed_toml_edit_value(raw: &str, kind: ValueKind) -> Result<TomlEditValue, AppError> {
+    let trimmed = raw.trim();
+    let invalid = |type_name: &str| {
+        AppError::config_error(format!("Value '{trimmed}' is not a valid {type_name}"))
+    };
+    match kind {
+        ValueKind::String => Ok(TomlEditValue::from(raw)),
+        ValueKind::Integer => {
+            trimmed.parse::<i64>().map(TomlEditValue::from).map_err(|_| invalid("integer"))
+        }
+        ValueKind::Float => {
+            trimmed.parse::<f64>().map(TomlEditValue::from).map_err(|_| invalid("float"))
+        }
+        ValueKind::Boolean => {
+            trimmed.parse::<bool>().map(TomlEditValue::from).map_err(|_| invalid("boolean"))
+        }
     }
 }
 
 pub fn infer_toml_edit_value(raw: &str) -> TomlEditValue {
     let trimmed = raw.trim();
+    if trimmed.starts_with('[')
+        && trimmed.ends_with(']')
+        && let Some(array) = parse_toml_array(trimmed)
+    {
+        return array;
+    }
     if let Ok(boolean) = trimmed.parse::<bool>() {
         return TomlEditValue::from(boolean);
     }
@@ -157,6 +775,58 @@ mod tests {
     use crate::core::paths;
     use crate::core::test_support::TestProject;
 
+    #[test]
+    fn public_bind_hosts_warn_unless_acknowledged() {
+        let warning = check_public_bind("ollama_server", "0.0.0.0", false, false)
+            .unwrap()
+            .expect("0.0.0.0 should warn");
+        assert!(warning.contains("ollama_server.host is 0.0.0.0"), "warning: {warning}");
+        assert!(check_public_bind("mlx_server", "[::]", false, false).unwrap().is_some());
+
+        assert_eq!(check_public_bind("ollama_server", "0.0.0.0", true, true).unwrap(), None);
+        assert_eq!(check_public_bind("ollama_server", "127.0.0.1", false, true).unwrap(), None);
+        assert_eq!(check_public_bind("mlx_server", "::1", false, true).unwrap(), None);
+        assert!(check_public_bind("ollama_server", "0.0.0.0", false, true).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    #[serial_test::serial]
+    fn config_permissions_are_restricted_and_checked() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let _project = TestProject::new();
+        ensure_config_exists().expect("config should be created");
+        let path = paths::user_config_file().unwrap();
+        assert_eq!(fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+        assert_eq!(check_config_permissions(&path, true).unwrap(), None);
+
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+        let warning = check_config_permissions(&path, false).unwrap().expect("should warn");
+        assert!(warning.contains("0644"), "warning: {warning}");
+        assert!(check_config_permissions(&path, true).is_err());
+
+        // Importing over a loose file, or into a missing one, leaves it private.
+        let contents = fs::read_to_string(&path).unwrap().replace("11434", "11500");
+        assert!(import_config(&contents, true).unwrap());
+        assert_eq!(fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+        fs::remove_file(&path).unwrap();
+        assert!(import_config(&contents, false).unwrap());
+        assert_eq!(fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+    }
+
+    #[test]
+    fn parse_env_assignment_requires_equals() {
+        assert_eq!(
+            parse_env_assignment("CUDA_VISIBLE_DEVICES=1"),
+            Ok(("CUDA_VISIBLE_DEVICES".into(), "1".into()))
+        );
+        assert_eq!(parse_env_assignment("OPTS=a=b"), Ok(("OPTS".into(), "a=b".into())));
+        assert_eq!(parse_env_assignment("EMPTY="), Ok(("EMPTY".into(), String::new())));
+        assert!(parse_env_assignment("NOVALUE").is_err());
+        assert!(parse_env_assignment("=1").is_err());
+    }
+
     #[test]
     #[serial_test::serial]
     fn load_config_creates_default_file() {
@@ -198,6 +868,186 @@ mod tests {
         assert_eq!(cfg.ollama_server.model, "custom-model");
     }
 
+    #[test]
+    fn diff_configs_reports_only_changed_keys() {
+        let defaults = Config::default();
+        let mut current = Config::default();
+        current.mlx_server.port = 9090;
+
+        let diff = diff_configs(&defaults, &current).expect("diff should succeed");
+        assert_eq!(diff.len(), 1);
+        assert_eq!(diff[0].key, "mlx_server.port");
+        assert_eq!(diff[0].default, Some(TomlValue::Integer(8080)));
+        assert_eq!(diff[0].current, Some(TomlValue::Integer(9090)));
+
+        current.ollama_server.extra.insert("OLLAMA_DEBUG".into(), TomlValue::Boolean(true));
+        current.ollama_server.extra.remove("OLLAMA_KEEP_ALIVE");
+        let keys: Vec<_> =
+            diff_configs(&defaults, &current).unwrap().into_iter().map(|d| d.key).collect();
+        assert_eq!(
+            keys,
+            ["mlx_server.port", "ollama_server.OLLAMA_DEBUG", "ollama_server.OLLAMA_KEEP_ALIVE"]
+        );
+    }
+
+    #[test]
+    fn document_leaves_lists_default_keys() {
+        let document = default_config_document().expect("default document should serialise");
+        let leaves = document_leaves(&document);
+        let keys: Vec<&str> = leaves.iter().map(|leaf| leaf.key.as_str()).collect();
+
+        for expected in [
+            "ollama_server.host",
+            "ollama_server.port",
+            "ollama_server.model",
+            "ollama_server.OLLAMA_KEEP_ALIVE",
+            "mlx_server.host",
+            "mlx_server.port",
+            "mlx_server.model",
+        ] {
+            assert!(keys.contains(&expected), "missing {expected} in {keys:?}");
+        }
+        let port = leaves.iter().find(|leaf| leaf.key == "mlx_server.port").unwrap();
+        assert_eq!(value_type_name(&port.value), "integer");
+        assert_eq!(document_value(&document, "mlx_server.port").unwrap().as_integer(), Some(8080));
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn set_config_value_validates_before_writing() {
+        let _project = TestProject::new();
+        set_config_value("ollama_server.port", "12001", None).expect("known key should be set");
+        assert_eq!(load_config().unwrap().ollama_server.port, 12001);
+
+        assert!(set_config_value("ollama_sever.port", "9000", None).is_err());
+        assert!(set_config_value("ollama_server.port", "not-a-port", None).is_err());
+        assert_eq!(load_config().unwrap().ollama_server.port, 12001);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn migrate_fills_new_defaults_and_keeps_user_values() {
+        let _project = TestProject::new();
+        let path = paths::user_config_file().unwrap();
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(
+            &path,
+            "# tuned for the GPU box\n[ollama_server]\nport = 11500 # shared with CI\nOLLAMA_DEBUG = \"1\"\n\n[mlx_server]\nmodel = \"m\"\n",
+        )
+        .unwrap();
+
+        assert_eq!(migrate_config().unwrap(), Some(0));
+        let migrated = load_config().unwrap();
+        assert_eq!(migrated.version, CONFIG_VERSION);
+        assert_eq!(migrated.ollama_server.port, 11500);
+        assert_eq!(migrated.ollama_server.scheme, DEFAULT_SCHEME);
+        assert_eq!(migrated.mlx_server.model, "m");
+        assert_eq!(migrated.mlx_server.port, DEFAULT_MLX_PORT);
+        let contents = fs::read_to_string(&path).unwrap();
+        assert!(contents.contains("OLLAMA_DEBUG"), "extra key should survive: {contents}");
+        assert!(contents.contains("extra_args"), "new fields should be written: {contents}");
+        assert!(
+            contents.contains("# tuned for the GPU box"),
+            "comments should survive: {contents}"
+        );
+        assert!(
+            contents.contains("port = 11500 # shared with CI"),
+            "comments should survive: {contents}"
+        );
+
+        assert_eq!(migrate_config().unwrap(), None);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn expand_env_vars_substitutes_and_rejects_unset() {
+        unsafe {
+            // SAFETY: tests run serially and remove the variable afterwards.
+            std::env::set_var("FUSION_TEST_TOKEN", "s3cret");
+        }
+        assert_eq!(expand_env_vars("Bearer ${FUSION_TEST_TOKEN}").unwrap(), "Bearer s3cret");
+        assert_eq!(expand_env_vars("plain").unwrap(), "plain");
+        unsafe {
+            // SAFETY: see above.
+            std::env::remove_var("FUSION_TEST_TOKEN");
+        }
+        assert!(expand_env_vars("Bearer ${FUSION_TEST_TOKEN}").is_err());
+        assert!(expand_env_vars("${UNTERMINATED").is_err());
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn headers_round_trip_without_leaking_into_env() {
+        let _project = TestProject::new();
+        set_config_value("ollama_server.headers.Authorization", "Bearer ${TOKEN}", None).unwrap();
+        let cfg = load_config().unwrap();
+        assert_eq!(cfg.ollama_server.headers["Authorization"], "Bearer ${TOKEN}");
+        assert!(!server_env(&cfg.ollama_server.extra, "OLLAMA_").contains_key("OLLAMA_HEADERS"));
+    }
+
+    #[test]
+    fn keep_alive_specs_parse_ollama_forms() {
+        let minutes = |m: u64| KeepAlive::For(Duration::from_secs(m * 60));
+        assert_eq!(parse_duration_spec("10m").unwrap(), minutes(10));
+        assert_eq!(parse_duration_spec("1h30m").unwrap(), minutes(90));
+        assert_eq!(parse_duration_spec("300").unwrap(), minutes(5));
+        assert_eq!(parse_duration_spec("0").unwrap(), KeepAlive::Unload);
+        assert_eq!(parse_duration_spec("0s").unwrap(), KeepAlive::Unload);
+        assert_eq!(parse_duration_spec("-1").unwrap(), KeepAlive::Forever);
+        assert_eq!(parse_duration_spec("-1m").unwrap(), KeepAlive::Forever);
+
+        for invalid in ["10x", "", "m", "-", "1h30", "ten", "1.5h"] {
+            assert!(parse_duration_spec(invalid).is_err(), "{invalid:?} should be rejected");
+        }
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn set_config_value_validates_keep_alive() {
+        let _project = TestProject::new();
+        set_config_value("ollama_server.keep_alive", "1h30m", None).expect("valid keep-alive");
+        assert!(set_config_value("ollama_server.OLLAMA_KEEP_ALIVE", "10x", None).is_err());
+        let document = load_config_document().unwrap();
+        assert_eq!(
+            document_value(&document, "ollama_server.keep_alive").unwrap().as_str(),
+            Some("1h30m")
+        );
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn set_config_value_honours_explicit_types() {
+        let _project = TestProject::new();
+        set_config_value("ollama_server.model", "3.2", Some(ValueKind::String))
+            .expect("forced string should be accepted");
+        assert_eq!(load_config().unwrap().ollama_server.model, "3.2");
+
+        set_config_value("ollama_server.OLLAMA_SCALE", "3.2", None).unwrap();
+        let document = load_config_document().unwrap();
+        let inferred = document_value(&document, "ollama_server.OLLAMA_SCALE").unwrap();
+        assert_eq!(inferred.as_float(), Some(3.2));
+
+        assert!(typed_toml_edit_value("true", ValueKind::String).unwrap().is_str());
+        assert!(typed_toml_edit_value("yes", ValueKind::Boolean).is_err());
+        assert!(typed_toml_edit_value("1.5", ValueKind::Integer).is_err());
+        assert_eq!(typed_toml_edit_value("7", ValueKind::Float).unwrap().as_float(), Some(7.0));
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn extra_args_round_trip_through_config_set() {
+        let _project = TestProject::new();
+        set_config_value(
+            "mlx_server.extra_args",
+            r#"["--trust-remote-code","--max-tokens","8192"]"#,
+            None,
+        )
+        .expect("array value should be accepted");
+        let cfg = load_config().unwrap();
+        assert_eq!(cfg.mlx_server.extra_args, ["--trust-remote-code", "--max-tokens", "8192"]);
+        assert!(!cfg.mlx_server.extra.contains_key("extra_args"));
+    }
+
     #[test]
     fn server_env_prefixes_missing_keys() {
         let mut extra = BTreeMap::new();
@@ -206,6 +1056,43 @@ mod tests {
         assert_eq!(env.get("OLLAMA_KEEP_ALIVE"), Some(&"5m".to_string()));
     }
 
+    #[test]
+    fn server_env_prefers_the_prefixed_key_on_collision() {
+        let mut extra = BTreeMap::new();
+        extra.insert("OLLAMA_KEEP_ALIVE".into(), TomlValue::String("10m".into()));
+        extra.insert("keep_alive".into(), TomlValue::String("5m".into()));
+        extra.insert("num_parallel".into(), TomlValue::Integer(2));
+        let env = server_env(&extra, "OLLAMA_");
+        assert_eq!(env.get("OLLAMA_KEEP_ALIVE"), Some(&"10m".to_string()));
+        assert_eq!(env.get("OLLAMA_NUM_PARALLEL"), Some(&"2".to_string()));
+
+        assert_eq!(
+            env_key_collisions(&extra, "OLLAMA_"),
+            [(
+                "OLLAMA_KEEP_ALIVE".to_string(),
+                vec!["OLLAMA_KEEP_ALIVE".into(), "keep_alive".into()]
+            )]
+        );
+        let mut cfg = Config::default();
+        cfg.ollama_server.extra.extend(extra);
+        let err = cfg.check_env_collisions().expect_err("collision should be rejected");
+        assert!(
+            err.to_string().contains("OLLAMA_KEEP_ALIVE, keep_alive all set OLLAMA_KEEP_ALIVE")
+        );
+        assert!(Config::default().check_env_collisions().is_ok());
+    }
+
+    #[test]
+    fn host_port_round_trips_ipv6() {
+        assert_eq!(format_host_port("::1", 8080), "[::1]:8080");
+        assert_eq!(format_host_port("[::1]", 8080), "[::1]:8080");
+        assert_eq!(format_host_port("127.0.0.1", 8080), "127.0.0.1:8080");
+        assert_eq!(parse_host_port("[::1]:8080"), Some(("::1".to_string(), 8080)));
+        assert_eq!(parse_host_port("localhost:11434"), Some(("localhost".to_string(), 11434)));
+        assert_eq!(parse_host_port("::1:8080"), None);
+        assert_eq!(parse_host_port(&format_host_port("fe80::1", 1)), Some(("fe80::1".into(), 1)));
+    }
+
     #[test]
     fn infer_toml_edit_value_detects_types() {
         let bool_value = infer_toml_edit_value("true");
@@ -216,5 +1103,42 @@ mod tests {
         assert!((float_value.as_float().unwrap() - 1.25).abs() < f64::EPSILON);
         let string_value = infer_toml_edit_value("hello");
         assert_eq!(string_value.as_str().unwrap(), "hello");
+        let array_value =
+            infer_toml_edit_value(r#"["--trust-remote-code", "--max-tokens", "8192"]"#);
+        let items: Vec<_> =
+            array_value.as_array().unwrap().iter().filter_map(|item| item.as_str()).collect();
+        assert_eq!(items, ["--trust-remote-code", "--max-tokens", "8192"]);
+        assert_eq!(infer_toml_edit_value("[not toml").as_str(), Some("[not toml"));
+    }
+
+    #[test]
+    fn duplicate_ports_are_rejected_naming_both_services() {
+        let mut cfg = Config::default();
+        cfg.mlx_server.port = cfg.ollama_server.port;
+        let err = cfg.validate().expect_err("shared port should be rejected");
+        let message = err.to_string();
+        assert!(message.contains("ollama_server") && message.contains("mlx_server"), "{message}");
+
+        cfg.mlx_server.host = "192.168.1.20".into();
+        assert!(cfg.validate().is_ok(), "different hosts may share a port");
+        cfg.ollama_server.host = "0.0.0.0".into();
+        assert!(cfg.validate().is_err(), "a wildcard bind overlaps every host");
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn ready_settings_are_validated_on_set() {
+        let _project = TestProject::new();
+        set_config_value("mlx_server.ready_path", "/health", None).unwrap();
+        set_config_value("mlx_server.ready_method", "GET", None).unwrap();
+        let cfg = load_config().unwrap();
+        assert_eq!(cfg.mlx_server.ready_path.as_deref(), Some("/health"));
+        assert_eq!(cfg.mlx_server.ready_method, Some(ReadyMethod::Get));
+        assert!(!server_env(&cfg.mlx_server.extra, "MLX_").contains_key("MLX_READY_PATH"));
+
+        assert!(set_config_value("mlx_server.ready_method", "PATCH", None).is_err());
+        let mut cfg = Config::default();
+        cfg.ollama_server.ready_path = Some("health".into());
+        assert!(cfg.validate().is_err());
     }
 }
@@ -12,8 +12,23 @@ pub struct OllamaServerConfig {
     pub host: String,
     #[serde(default = "default_ollama_port")]
     pub port: u16,
+    /// URL scheme used to reach the server: `http`, or `https` behind a TLS proxy.
+    #[serde(default = "super::default_scheme")]
+    pub scheme: String,
     #[serde(default = "default_ollama_model")]
     pub model: String,
+    /// Additional arguments appended to the server command line.
+    #[serde(default)]
+    pub extra_args: Vec<String>,
+    /// HTTP headers sent with every request to the server; values may reference `${VAR}`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+    /// Endpoint polled for readiness instead of the built-in chat completion probe.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ready_path: Option<String>,
+    /// Method for `ready_path`: `POST` sends a one-token chat request, `GET` expects any 2xx.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ready_method: Option<super::ReadyMethod>,
     #[serde(default = "default_ollama_server_extra")]
     #[serde(flatten)]
     pub extra: BTreeMap<String, TomlValue>,
@@ -24,7 +39,12 @@ impl Default for OllamaServerConfig {
         Self {
             host: default_ollama_host(),
             port: default_ollama_port(),
+            scheme: super::default_scheme(),
             model: default_ollama_model(),
+            extra_args: Vec::new(),
+            headers: BTreeMap::new(),
+            ready_path: None,
+            ready_method: None,
             extra: default_ollama_server_extra(),
         }
     }
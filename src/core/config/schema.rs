@@ -0,0 +1,172 @@
+use super::{Config, default_config_document, document_leaves};
+use crate::error::AppError;
+use serde_json::{Map, Value as JsonValue, json};
+use toml::Value as TomlValue;
+
+/// Sections whose flattened `extra` map accepts arbitrary keys (forwarded as environment).
+const EXTRA_SECTIONS: [&str; 2] = ["ollama_server", "mlx_server"];
+
+/// Largest edit distance at which a known key is offered as a suggestion.
+const SUGGESTION_DISTANCE: usize = 3;
+
+/// Check a dotted key against the default configuration schema before it is written.
+///
+/// Known keys are accepted, as are new single-segment keys inside a server section's `extra`
+/// map; anything else is rejected with the closest known key as a suggestion.
+pub fn validate_config_key(key: &str) -> Result<(), AppError> {
+    let known: Vec<String> =
+        document_leaves(&default_config_document()?).into_iter().map(|leaf| leaf.key).collect();
+    if known.iter().any(|candidate| candidate == key) {
+        return Ok(());
+    }
+
+    if let Some((section, rest)) = key.split_once('.')
+        && EXTRA_SECTIONS.contains(&section)
+        && rest.strip_prefix("headers.").is_some_and(|name| !name.is_empty() && !name.contains('.'))
+    {
+        return Ok(());
+    }
+
+    let suggestion = closest_key(key, &known);
+    if let Some((section, name)) = key.split_once('.')
+        && EXTRA_SECTIONS.contains(&section)
+        && !name.is_empty()
+        && !name.contains('.')
+    {
+        // Allow free-form extras unless the name is an obvious misspelling of a structured field.
+        let misspelled = suggestion.as_ref().is_some_and(|(candidate, distance)| {
+            *distance <= 2 && candidate.starts_with(&format!("{section}."))
+        }) && name.chars().all(|c| !c.is_ascii_uppercase());
+        if !misspelled {
+            return Ok(());
+        }
+    }
+
+    let mut message = format!("Unknown configuration key '{key}'");
+    if let Some((candidate, _)) = suggestion {
+        message.push_str(&format!("; did you mean `{candidate}`?"));
+    }
+    Err(AppError::config_error(message))
+}
+
+/// JSON Schema (draft 2020-12) for `config.toml`, generated from the defaults so it cannot drift
+/// from the structured fields. Optional fields absent from the defaults are added by hand, and
+/// server sections accept any further key as an environment variable for the server.
+pub fn config_json_schema() -> Result<JsonValue, AppError> {
+    let defaults = TomlValue::try_from(Config::default())
+        .map_err(|err| AppError::config_error(format!("Failed to serialise config: {err}")))?;
+    let mut schema = value_schema("", &defaults);
+    schema["$schema"] = json!("https://json-schema.org/draft/2020-12/schema");
+    schema["title"] = json!("fusion config.toml");
+    for section in EXTRA_SECTIONS {
+        let section = &mut schema["properties"][section];
+        let properties = section["properties"].as_object_mut().expect("sections are tables");
+        properties.insert(
+            "headers".into(),
+            json!({
+                "type": "object",
+                "description": "HTTP headers sent with every request; values may reference ${VAR}.",
+                "additionalProperties": { "type": "string" },
+            }),
+        );
+        properties.insert(
+            "ready_path".into(),
+            json!({
+                "type": "string",
+                "pattern": "^/",
+                "description": "Endpoint polled for readiness instead of the chat completion probe.",
+            }),
+        );
+        properties.insert(
+            "ready_method".into(),
+            json!({ "enum": ["GET", "POST", "get", "post"], "default": "POST" }),
+        );
+        section["additionalProperties"] = json!({
+            "type": ["string", "integer", "number", "boolean"],
+            "description": "Forwarded to the server as an environment variable.",
+        });
+    }
+    schema["additionalProperties"] = json!(true);
+    Ok(schema)
+}
+
+fn value_schema(key: &str, value: &TomlValue) -> JsonValue {
+    let mut schema = match value {
+        TomlValue::Table(table) => {
+            let properties: Map<String, JsonValue> = table
+                .iter()
+                .map(|(name, child)| (name.clone(), value_schema(name, child)))
+                .collect();
+            return json!({ "type": "object", "properties": properties });
+        }
+        TomlValue::String(_) => json!({ "type": "string" }),
+        TomlValue::Integer(_) => json!({ "type": "integer" }),
+        TomlValue::Float(_) => json!({ "type": "number" }),
+        TomlValue::Boolean(_) => json!({ "type": "boolean" }),
+        TomlValue::Datetime(_) => json!({ "type": "string", "format": "date-time" }),
+        TomlValue::Array(_) => json!({ "type": "array", "items": { "type": "string" } }),
+    };
+    if key == "port" {
+        schema["minimum"] = json!(1);
+        schema["maximum"] = json!(65535);
+    }
+    schema["default"] = serde_json::to_value(value).unwrap_or(JsonValue::Null);
+    schema
+}
+
+fn closest_key(key: &str, known: &[String]) -> Option<(String, usize)> {
+    known
+        .iter()
+        .map(|candidate| (candidate.clone(), edit_distance(key, candidate)))
+        .min_by_key(|(_, distance)| *distance)
+        .filter(|(_, distance)| *distance <= SUGGESTION_DISTANCE)
+}
+
+/// Levenshtein distance between two strings, counted in characters.
+fn edit_distance(left: &str, right: &str) -> usize {
+    let right: Vec<char> = right.chars().collect();
+    let mut previous: Vec<usize> = (0..=right.len()).collect();
+    for (i, left_char) in left.chars().enumerate() {
+        let mut current = vec![i + 1; right.len() + 1];
+        for (j, right_char) in right.iter().enumerate() {
+            let substitution = previous[j] + usize::from(left_char != *right_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[right.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_accepts_known_keys() {
+        validate_config_key("ollama_server.port").expect("known key should be accepted");
+        validate_config_key("mlx_server.model").expect("known key should be accepted");
+    }
+
+    #[test]
+    fn validate_rejects_typo_with_suggestion() {
+        let err = validate_config_key("ollama_sever.port").expect_err("typo should be rejected");
+        assert!(err.to_string().contains("did you mean `ollama_server.port`?"), "{err}");
+
+        let err = validate_config_key("mlx_server.prot").expect_err("field typo should fail");
+        assert!(err.to_string().contains("`mlx_server.port`"), "{err}");
+    }
+
+    #[test]
+    fn validate_allows_extra_map_keys() {
+        validate_config_key("ollama_server.OLLAMA_DEBUG").expect("extra key should be allowed");
+        validate_config_key("mlx_server.MLX_TRUST_REMOTE_CODE").expect("extra key allowed");
+        assert!(validate_config_key("mlx_server.nested.key").is_err());
+    }
+
+    #[test]
+    fn edit_distance_counts_single_edits() {
+        assert_eq!(edit_distance("port", "port"), 0);
+        assert_eq!(edit_distance("prot", "port"), 2);
+        assert_eq!(edit_distance("ollama_sever", "ollama_server"), 1);
+    }
+}
@@ -0,0 +1,183 @@
+use crate::core::config::{self, Config};
+use crate::core::services::{self, ManagedService};
+use crate::core::{integration, paths, process};
+use std::{env, fs};
+
+/// Severity of a single diagnostic. Only `Fail` makes `fusion doctor` exit non-zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+/// Outcome of one environment check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckResult {
+    pub name: String,
+    pub status: CheckStatus,
+    pub detail: String,
+}
+
+impl CheckResult {
+    fn new(name: impl Into<String>, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self { name: name.into(), status, detail: detail.into() }
+    }
+}
+
+/// Run every check, falling back to default settings when the config file cannot be loaded.
+pub fn run_checks() -> Vec<CheckResult> {
+    let mut results = vec![check_config_dir()];
+    let (config_result, cfg) = check_config_file();
+    results.push(config_result);
+    results.push(check_pid_dir_writable());
+    results.push(check_editor(env::var("EDITOR").ok().as_deref()));
+
+    let services = [
+        services::create_ollama_service(&cfg.ollama_server),
+        services::create_mlx_service(&cfg.mlx_server),
+    ];
+    for service in &services {
+        results.push(check_binary(service));
+    }
+    for service in &services {
+        results.push(check_endpoint(service));
+    }
+    results
+}
+
+pub fn check_config_dir() -> CheckResult {
+    match paths::user_config_dir() {
+        Ok(dir) => CheckResult::new("config dir", CheckStatus::Pass, dir.display().to_string()),
+        Err(err) => CheckResult::new("config dir", CheckStatus::Fail, err.to_string()),
+    }
+}
+
+/// Parse the config file, returning the loaded settings (or defaults) alongside the result.
+pub fn check_config_file() -> (CheckResult, Config) {
+    match config::load_config() {
+        Ok(cfg) => {
+            let detail = paths::user_config_file()
+                .map(|path| path.display().to_string())
+                .unwrap_or_default();
+            (CheckResult::new("config file", CheckStatus::Pass, detail), cfg)
+        }
+        Err(err) => {
+            (CheckResult::new("config file", CheckStatus::Fail, err.to_string()), Config::default())
+        }
+    }
+}
+
+pub fn check_pid_dir_writable() -> CheckResult {
+    let probe = paths::ensure_pid_dir().and_then(|dir| {
+        let path = dir.join(".doctor-probe");
+        fs::write(&path, b"")?;
+        fs::remove_file(&path)?;
+        Ok(dir)
+    });
+    match probe {
+        Ok(dir) => CheckResult::new("state dir", CheckStatus::Pass, dir.display().to_string()),
+        Err(err) => {
+            CheckResult::new("state dir", CheckStatus::Fail, format!("not writable: {err}"))
+        }
+    }
+}
+
+/// `$EDITOR` is only needed to edit the config file by hand, so problems with it only warn.
+pub fn check_editor(editor: Option<&str>) -> CheckResult {
+    let Some(editor) = editor.map(str::trim).filter(|editor| !editor.is_empty()) else {
+        return CheckResult::new("editor", CheckStatus::Warn, "$EDITOR is not set");
+    };
+    // Editors are often configured with flags, e.g. `code --wait`.
+    let program = editor.split_whitespace().next().unwrap_or(editor);
+    match integration::resolve_executable(program).filter(|path| path.is_file()) {
+        Some(path) => CheckResult::new("editor", CheckStatus::Pass, path.display().to_string()),
+        None => CheckResult::new(
+            "editor",
+            CheckStatus::Warn,
+            format!("$EDITOR `{program}` not found on PATH"),
+        ),
+    }
+}
+
+/// A missing runtime binary only warns, since most users run just one of the backends.
+pub fn check_binary(service: &ManagedService) -> CheckResult {
+    let name = format!("{} binary", service.name);
+    let Some(program) = service.command.first() else {
+        return CheckResult::new(name, CheckStatus::Fail, "no command configured");
+    };
+    match integration::resolve_executable(program).filter(|path| path.is_file()) {
+        Some(path) => CheckResult::new(name, CheckStatus::Pass, path.display().to_string()),
+        None => CheckResult::new(name, CheckStatus::Warn, format!("`{program}` not found on PATH")),
+    }
+}
+
+/// Report whether anything listens on the configured endpoint; not running is only a warning.
+pub fn check_endpoint(service: &ManagedService) -> CheckResult {
+    let name = format!("{} endpoint", service.name);
+    let endpoint = config::format_host_port(&service.host, service.port);
+    if process::is_port_open(service) {
+        CheckResult::new(name, CheckStatus::Pass, format!("{endpoint} is accepting connections"))
+    } else {
+        CheckResult::new(name, CheckStatus::Warn, format!("nothing listening on {endpoint}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_support::TestProject;
+    use std::net::TcpListener;
+
+    fn service_with(command: &str, port: u16) -> ManagedService {
+        let cfg = Config::default();
+        let mut service = services::create_ollama_service(&cfg.ollama_server);
+        service.command = vec![command.to_string()];
+        service.port = port;
+        service
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn config_and_state_dir_checks_pass_in_isolated_project() {
+        let _project = TestProject::new();
+        assert_eq!(check_config_dir().status, CheckStatus::Pass);
+        assert_eq!(check_config_file().0.status, CheckStatus::Pass);
+        assert_eq!(check_pid_dir_writable().status, CheckStatus::Pass);
+    }
+
+    #[test]
+    fn missing_binary_warns() {
+        let result = check_binary(&service_with("fusion-definitely-missing-binary", 1));
+        assert_eq!(result.status, CheckStatus::Warn);
+        assert!(result.detail.contains("not found on PATH"));
+
+        assert_eq!(check_binary(&service_with("sh", 1)).status, CheckStatus::Pass);
+    }
+
+    #[test]
+    fn editor_check_only_warns() {
+        assert_eq!(check_editor(None).status, CheckStatus::Warn);
+        assert_eq!(check_editor(Some("  ")).status, CheckStatus::Warn);
+        let missing = check_editor(Some("fusion-definitely-missing-editor --wait"));
+        assert_eq!(missing.status, CheckStatus::Warn);
+        assert!(
+            missing.detail.contains("`fusion-definitely-missing-editor`"),
+            "{}",
+            missing.detail
+        );
+
+        assert_eq!(check_editor(Some("sh -e")).status, CheckStatus::Pass);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn endpoint_check_reflects_listener() {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("listener should bind");
+        let port = listener.local_addr().unwrap().port();
+        assert_eq!(check_endpoint(&service_with("ollama", port)).status, CheckStatus::Pass);
+
+        drop(listener);
+        assert_eq!(check_endpoint(&service_with("ollama", port)).status, CheckStatus::Warn);
+    }
+}
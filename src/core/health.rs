@@ -1,92 +1,670 @@
-use crate::core::config;
-use crate::core::services::ManagedService;
+use crate::core::config::ReadyMethod;
+use crate::core::services::{HealthEndpoint, ManagedService, ReadyCheck};
+use crate::core::{http, http_log, process};
 use crate::error::AppError;
-use reqwest::blocking::Client;
+use reqwest::StatusCode;
 use serde_json::json;
-use std::time::Duration;
+use std::fmt::{self, Display};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::ops::ControlFlow;
+use std::time::{Duration, Instant};
+
+const BODY_PREVIEW_CHARS: usize = 200;
+const DEFAULT_STARTUP_TIMEOUT_SECS: u64 = 300;
+const DEFAULT_POLL_INTERVAL_MS: u64 = 1000;
+/// Floor for `FUSION_POLL_INTERVAL_MS` so a 0 or tiny value cannot turn the wait into a busy loop.
+const MIN_POLL_INTERVAL_MS: u64 = 50;
+
+/// Build the OpenAI-compatible chat completions endpoint for a service.
+pub fn chat_completions_url(service: &ManagedService) -> String {
+    endpoint_url(service, HealthEndpoint::OpenAiChat)
+}
 
 /// Sends an inference request and returns the generated text content.
+#[tracing::instrument(skip_all, fields(service = service.name, model = model_name))]
 pub fn query_inference(
     service: &ManagedService,
     model_name: &str,
     prompt: &str,
     timeout_secs: u64,
 ) -> Result<String, AppError> {
-    let client = Client::builder()
-        .timeout(Duration::from_secs(timeout_secs))
-        .build()
-        .map_err(|e| AppError::process_error(service.name, format!("Client build error: {e}")))?;
+    let client = http::client_for(service, Some(Duration::from_secs(timeout_secs)))?;
+
+    let mut endpoints = service.health_endpoints().iter().peekable();
+    while let Some(&endpoint) = endpoints.next() {
+        let url = endpoint_url(service, endpoint);
+        let payload = inference_payload(endpoint, model_name, prompt, None);
+        let response = http::send_idempotent_with_retry(|| client.post(&url).json(&payload).send())
+            .map_err(|e| AppError::http_source(service.name, "Connection failed", e))?;
+        let status = response.status();
+        let text = response
+            .text()
+            .map_err(|e| AppError::http_source(service.name, "Failed to read response", e))?;
+        http_log::record(service.name, "POST", &url, Some(&payload), status.as_u16(), &text);
+
+        if status == StatusCode::NOT_FOUND && endpoints.peek().is_some() {
+            tracing::debug!(path = endpoint.path(), "endpoint not found; trying fallback");
+            continue;
+        }
+        if !status.is_success() {
+            return Err(AppError::http_error(
+                service.name,
+                format!("Service responded with status: {status}"),
+            ));
+        }
+
+        let body: serde_json::Value = serde_json::from_str(&text).map_err(|e| {
+            AppError::http_error(service.name, format!("Failed to parse JSON response: {e}"))
+        })?;
+        return response_content(endpoint, &body).map(str::to_string).ok_or_else(|| {
+            AppError::http_error(
+                service.name,
+                format!("backend returned no completion content: {}", body_preview(&text)),
+            )
+        });
+    }
+    Err(AppError::http_error(service.name, "No health endpoint available"))
+}
+
+/// Shorten a response body for error messages, keeping enough to recognise its shape.
+fn body_preview(body: &str) -> String {
+    let body = body.trim();
+    match body.char_indices().nth(BODY_PREVIEW_CHARS) {
+        Some((cut, _)) => format!("{}…", &body[..cut]),
+        None => body.to_string(),
+    }
+}
 
-    let url = format!(
-        "http://{}/v1/chat/completions",
-        config::format_host_port(&service.host, service.port),
-    );
+fn endpoint_url(service: &ManagedService, endpoint: HealthEndpoint) -> String {
+    service.url(endpoint.path())
+}
 
-    let payload = json!({
+/// Chat request for `endpoint`, optionally capped at `max_tokens` generated tokens.
+fn inference_payload(
+    endpoint: HealthEndpoint,
+    model_name: &str,
+    prompt: &str,
+    max_tokens: Option<u32>,
+) -> serde_json::Value {
+    let mut payload = json!({
         "model": model_name,
         "messages": [
             { "role": "user", "content": prompt }
         ],
         "stream": false,
     });
+    if let Some(max_tokens) = max_tokens {
+        match endpoint {
+            HealthEndpoint::OpenAiChat => payload["max_tokens"] = json!(max_tokens),
+            HealthEndpoint::OllamaChat => payload["options"] = json!({ "num_predict": max_tokens }),
+        }
+    }
+    payload
+}
 
-    let response =
-        client.post(&url).json(&payload).send().map_err(|e| {
-            AppError::process_error(service.name, format!("Connection failed: {e}"))
-        })?;
+fn response_content(endpoint: HealthEndpoint, body: &serde_json::Value) -> Option<&str> {
+    match endpoint {
+        HealthEndpoint::OpenAiChat => body["choices"][0]["message"]["content"].as_str(),
+        HealthEndpoint::OllamaChat => body["message"]["content"].as_str(),
+    }
+}
+
+/// Measure how long the service takes to answer a plain `GET /`, without running inference.
+///
+/// Any HTTP response counts as up, since runtimes differ in what they serve at the root.
+#[tracing::instrument(skip_all, fields(service = service.name))]
+pub fn ping(service: &ManagedService, timeout: Duration) -> Result<Duration, AppError> {
+    let client = http::client_for(service, Some(timeout))?;
+    let started = Instant::now();
+    client
+        .get(service.url("/"))
+        .send()
+        .map_err(|e| AppError::http_source(service.name, "Connection failed", e))?;
+    Ok(started.elapsed())
+}
+
+/// Why a readiness probe did not succeed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReadinessError {
+    /// No HTTP exchange happened: the server is not accepting connections yet.
+    Unreachable(String),
+    /// The server answered with a non-success status, typically while the model is loading.
+    NotReady { status: u16 },
+}
 
-    if !response.status().is_success() {
-        return Err(AppError::process_error(
-            service.name,
-            format!("Service responded with status: {}", response.status()),
-        ));
+impl Display for ReadinessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReadinessError::Unreachable(message) => write!(f, "Connection failed: {message}"),
+            ReadinessError::NotReady { status } => {
+                write!(f, "Service responded with status: {status}")
+            }
+        }
     }
+}
+
+/// How `up` decides that a started server is ready.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReadinessProbe {
+    /// A one-token completion must succeed.
+    #[default]
+    Inference,
+    /// The port accepting a TCP connection is enough, for backends fusion cannot prompt.
+    Port,
+}
 
-    let body: serde_json::Value = response.json().map_err(|e| {
-        AppError::process_error(service.name, format!("Failed to parse JSON response: {e}"))
-    })?;
+/// How long `up` waits for readiness: `FUSION_STARTUP_TIMEOUT_SECS` when it parses, else 300s.
+pub fn startup_timeout_secs() -> u64 {
+    if let Ok(value) = std::env::var("FUSION_STARTUP_TIMEOUT_SECS")
+        && let Ok(parsed) = value.parse::<u64>()
+    {
+        return parsed;
+    }
+    DEFAULT_STARTUP_TIMEOUT_SECS
+}
+
+/// Delay between readiness polls: `FUSION_POLL_INTERVAL_MS` when it parses, clamped to a minimum.
+pub fn poll_interval(raw: Option<&str>) -> Duration {
+    let millis =
+        raw.and_then(|value| value.trim().parse::<u64>().ok()).unwrap_or(DEFAULT_POLL_INTERVAL_MS);
+    Duration::from_millis(millis.max(MIN_POLL_INTERVAL_MS))
+}
 
-    body["choices"][0]["message"]["content"].as_str().map(|s| s.to_string()).ok_or_else(|| {
-        AppError::process_error(service.name, "Invalid response structure: missing content")
+/// Poll `probe` until `service`, running as `pid`, is ready. Fails when the process dies or
+/// `timeout_secs` passes. Prints nothing and installs no signal handler.
+pub fn wait_for_readiness(
+    service: &ManagedService,
+    pid: i32,
+    model_name: &str,
+    probe: ReadinessProbe,
+    timeout_secs: u64,
+) -> Result<(), AppError> {
+    wait_for_readiness_with(service, pid, model_name, probe, timeout_secs, |_| {
+        ControlFlow::Continue(())
     })
+    .map(|_| ())
+}
+
+/// One step of [`wait_for_readiness_with`], reported to its `on_poll` hook.
+pub enum ReadinessPoll<'a> {
+    /// About to probe the service.
+    Probing,
+    /// The last probe failed; the loop sleeps before the next one.
+    NotReady(&'a ReadinessError),
+    /// The service answered the probe.
+    Ready,
+}
+
+/// How a readiness wait ended when it did not fail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WaitOutcome {
+    Ready,
+    /// `on_poll` broke out of the wait before the service was ready.
+    Stopped,
+}
+
+/// [`wait_for_readiness`] with a hook called at each step, e.g. to print progress or echo logs.
+/// Returning `ControlFlow::Break` from the hook ends the wait with [`WaitOutcome::Stopped`].
+#[tracing::instrument(skip_all, fields(service = service.name, pid = pid))]
+pub fn wait_for_readiness_with(
+    service: &ManagedService,
+    pid: i32,
+    model_name: &str,
+    probe: ReadinessProbe,
+    timeout_secs: u64,
+    mut on_poll: impl FnMut(ReadinessPoll<'_>) -> ControlFlow<()>,
+) -> Result<WaitOutcome, AppError> {
+    let start = Instant::now();
+    let timeout = Duration::from_secs(timeout_secs);
+    let poll_interval = poll_interval(std::env::var("FUSION_POLL_INTERVAL_MS").ok().as_deref());
+    // Derive per-poll timeout from overall startup timeout, with a minimum of 2 seconds
+    let per_poll_timeout_secs = (timeout_secs / 10).max(2);
+    let mut last_error = None;
+
+    while start.elapsed() < timeout {
+        if on_poll(ReadinessPoll::Probing).is_break() {
+            return Ok(WaitOutcome::Stopped);
+        }
+        process::ensure_alive(service, pid)?;
+        match check_readiness(service, model_name, probe, per_poll_timeout_secs) {
+            Ok(()) => {
+                let _ = on_poll(ReadinessPoll::Ready);
+                return Ok(WaitOutcome::Ready);
+            }
+            Err(err) => {
+                tracing::debug!(error = %err, elapsed_ms = start.elapsed().as_millis() as u64, "not ready");
+                if on_poll(ReadinessPoll::NotReady(&err)).is_break() {
+                    return Ok(WaitOutcome::Stopped);
+                }
+                last_error = Some(err);
+                std::thread::sleep(poll_interval);
+            }
+        }
+    }
+
+    let message = match last_error {
+        Some(err) => format!("Timed out waiting for service to be ready ({err})."),
+        None => "Timed out waiting for service to be ready.".to_string(),
+    };
+    Err(AppError::process_error(service.name, message))
+}
+
+/// Run `probe` once against `service`.
+pub fn check_readiness(
+    service: &ManagedService,
+    model_name: &str,
+    probe: ReadinessProbe,
+    timeout_secs: u64,
+) -> Result<(), ReadinessError> {
+    match probe {
+        ReadinessProbe::Inference => check_inference_readiness(service, model_name, timeout_secs),
+        ReadinessProbe::Port => check_port_readiness(service, Duration::from_secs(timeout_secs)),
+    }
+}
+
+/// Ready once any address of `host:port` accepts a TCP connection.
+#[tracing::instrument(level = "debug", skip_all, fields(service = service.name))]
+pub fn check_port_readiness(
+    service: &ManagedService,
+    timeout: Duration,
+) -> Result<(), ReadinessError> {
+    let addrs = (service.host.as_str(), service.port)
+        .to_socket_addrs()
+        .map_err(|e| ReadinessError::Unreachable(e.to_string()))?;
+    let mut last_error = None;
+    for addr in addrs {
+        match TcpStream::connect_timeout(&addr, timeout) {
+            Ok(_) => return Ok(()),
+            Err(err) => last_error = Some(err.to_string()),
+        }
+    }
+    Err(ReadinessError::Unreachable(
+        last_error.unwrap_or_else(|| format!("{} did not resolve", service.host)),
+    ))
 }
 
 /// Sends a lightweight inference request to the specified service to check if it is ready.
+#[tracing::instrument(level = "debug", skip_all, fields(service = service.name))]
 pub fn check_inference_readiness(
     service: &ManagedService,
     model_name: &str,
     timeout_secs: u64,
-) -> Result<(), AppError> {
-    let client = Client::builder()
-        .timeout(Duration::from_secs(timeout_secs))
-        .build()
-        .map_err(|e| AppError::process_error(service.name, format!("Client build error: {e}")))?;
+) -> Result<(), ReadinessError> {
+    let client = http::client_for(service, Some(Duration::from_secs(timeout_secs)))
+        .map_err(|e| ReadinessError::Unreachable(e.to_string()))?;
+    if let Some(check) = &service.ready_check {
+        return check_configured_readiness(&client, service, check, model_name);
+    }
 
-    let url = format!(
-        "http://{}/v1/chat/completions",
-        config::format_host_port(&service.host, service.port),
-    );
+    let mut endpoints = service.health_endpoints().iter().peekable();
+    while let Some(&endpoint) = endpoints.next() {
+        let url = endpoint_url(service, endpoint);
+        let payload = inference_payload(endpoint, model_name, "ping", Some(1));
+        let response = http::send_idempotent_with_retry(|| client.post(&url).json(&payload).send())
+            .map_err(|e| ReadinessError::Unreachable(e.to_string()))?;
+        let status = response.status();
+        tracing::debug!(status = status.as_u16(), "readiness probe answered");
+        if http_log::is_enabled() {
+            let text = response.text().unwrap_or_default();
+            http_log::record(service.name, "POST", &url, Some(&payload), status.as_u16(), &text);
+        }
 
-    let payload = json!({
-        "model": model_name,
-        "messages": [
-            { "role": "user", "content": "ping" }
-        ],
-        "max_tokens": 1,
-        "stream": false,
-    });
-
-    let response =
-        client.post(&url).json(&payload).send().map_err(|e| {
-            AppError::process_error(service.name, format!("Connection failed: {e}"))
-        })?;
+        if status.is_success() {
+            return Ok(());
+        }
+        if status != StatusCode::NOT_FOUND || endpoints.peek().is_none() {
+            return Err(ReadinessError::NotReady { status: status.as_u16() });
+        }
+    }
+    Err(ReadinessError::Unreachable("no health endpoint available".into()))
+}
 
-    if response.status().is_success() {
+/// Probe the configured `ready_path`: a GET needs any 2xx, a POST sends the one-token ping.
+fn check_configured_readiness(
+    client: &reqwest::blocking::Client,
+    service: &ManagedService,
+    check: &ReadyCheck,
+    model_name: &str,
+) -> Result<(), ReadinessError> {
+    let url = service.url(&check.path);
+    let response = match check.method {
+        ReadyMethod::Get => http::send_idempotent_with_retry(|| client.get(&url).send()),
+        ReadyMethod::Post => {
+            let endpoint = if check.path == HealthEndpoint::OllamaChat.path() {
+                HealthEndpoint::OllamaChat
+            } else {
+                HealthEndpoint::OpenAiChat
+            };
+            let payload = inference_payload(endpoint, model_name, "ping", Some(1));
+            http::send_idempotent_with_retry(|| client.post(&url).json(&payload).send())
+        }
+    }
+    .map_err(|e| ReadinessError::Unreachable(e.to_string()))?;
+    let status = response.status();
+    tracing::debug!(status = status.as_u16(), path = %check.path, "readiness probe answered");
+    if status.is_success() {
         Ok(())
     } else {
-        Err(AppError::process_error(
-            service.name,
-            format!("Service responded with status: {}", response.status()),
-        ))
+        Err(ReadinessError::NotReady { status: status.as_u16() })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::{MlxServerConfig, OllamaServerConfig};
+    use crate::core::services;
+    use crate::core::test_support::TestProject;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    #[test]
+    fn poll_interval_falls_back_and_enforces_minimum() {
+        assert_eq!(poll_interval(None), Duration::from_millis(DEFAULT_POLL_INTERVAL_MS));
+        assert_eq!(poll_interval(Some("250")), Duration::from_millis(250));
+        assert_eq!(poll_interval(Some("fast")), Duration::from_millis(DEFAULT_POLL_INTERVAL_MS));
+        assert_eq!(poll_interval(Some("0")), Duration::from_millis(MIN_POLL_INTERVAL_MS));
+    }
+
+    #[test]
+    fn chat_completions_url_brackets_ipv6_hosts() {
+        for host in ["::1", "[::1]"] {
+            let cfg = MlxServerConfig { host: host.into(), ..MlxServerConfig::default() };
+            let service = services::create_mlx_service(&cfg);
+            assert_eq!(chat_completions_url(&service), "http://[::1]:8080/v1/chat/completions");
+        }
+    }
+
+    #[test]
+    fn readiness_reports_unreachable_when_nothing_listens() {
+        let port = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+        let cfg = MlxServerConfig { port, ..MlxServerConfig::default() };
+        let service = services::create_mlx_service(&cfg);
+
+        let result = check_inference_readiness(&service, &cfg.model, 1);
+        assert!(matches!(result, Err(ReadinessError::Unreachable(_))), "got {result:?}");
+    }
+
+    #[test]
+    fn readiness_reports_status_while_model_loads() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let cfg = MlxServerConfig {
+            port: listener.local_addr().unwrap().port(),
+            ..MlxServerConfig::default()
+        };
+        let service = services::create_mlx_service(&cfg);
+
+        let stub = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap() > 0 {
+                if line == "\r\n" {
+                    break;
+                }
+                line.clear();
+            }
+            let body = br#"{"error":"model loading"}"#;
+            let mut stream = reader.into_inner();
+            write!(
+                stream,
+                "HTTP/1.1 503 Service Unavailable\r\nContent-Length: {}\r\n\r\n",
+                body.len()
+            )
+            .unwrap();
+            stream.write_all(body).unwrap();
+        });
+
+        let result = check_inference_readiness(&service, &cfg.model, 5);
+        assert_eq!(result, Err(ReadinessError::NotReady { status: 503 }));
+        stub.join().unwrap();
+    }
+
+    #[test]
+    fn query_inference_retries_after_dropped_connection() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let cfg = MlxServerConfig {
+            port: listener.local_addr().unwrap().port(),
+            ..MlxServerConfig::default()
+        };
+        let service = services::create_mlx_service(&cfg);
+
+        let stub = thread::spawn(move || {
+            // First connection is closed without a response to simulate a reset during load.
+            drop(listener.accept().unwrap());
+
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap() > 0 {
+                if line == "\r\n" {
+                    break;
+                }
+                line.clear();
+            }
+            let body = br#"{"choices":[{"message":{"content":"recovered"}}]}"#;
+            let mut stream = reader.into_inner();
+            write!(stream, "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n", body.len()).unwrap();
+            stream.write_all(body).unwrap();
+        });
+
+        let response =
+            query_inference(&service, &cfg.model, "hi", 5).expect("retry should succeed");
+        assert_eq!(response, "recovered");
+        stub.join().unwrap();
+    }
+
+    /// Answer one request on `listener` with `status` and `body`, returning the request line.
+    fn respond(listener: &TcpListener, status: &str, body: &str) -> String {
+        respond_with_head(listener, status, body).swap_remove(0)
+    }
+
+    /// Like [`respond`], but returns the request line followed by every header line.
+    fn respond_with_head(listener: &TcpListener, status: &str, body: &str) -> Vec<String> {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream);
+        let mut head = Vec::new();
+        let mut content_length = 0;
+        let mut line = String::new();
+        while reader.read_line(&mut line).unwrap() > 0 && line != "\r\n" {
+            if let Some(value) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                content_length = value.trim().parse().unwrap();
+            }
+            head.push(line.trim_end().to_string());
+            line.clear();
+        }
+        let mut request_body = vec![0; content_length];
+        reader.read_exact(&mut request_body).unwrap();
+        let mut stream = reader.into_inner();
+        write!(
+            stream,
+            "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        )
+        .unwrap();
+        head
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn inference_sends_json_headers_and_configured_headers() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let mut cfg = MlxServerConfig {
+            port: listener.local_addr().unwrap().port(),
+            ..MlxServerConfig::default()
+        };
+        cfg.headers.insert("Authorization".into(), "Bearer ${FUSION_TEST_API_KEY}".into());
+        let service = services::create_mlx_service(&cfg);
+        let stub = thread::spawn(move || {
+            respond_with_head(&listener, "200 OK", r#"{"choices":[{"message":{"content":"ok"}}]}"#)
+        });
+
+        unsafe {
+            // SAFETY: serial test; the variable is removed before returning.
+            std::env::set_var("FUSION_TEST_API_KEY", "s3cret");
+        }
+        let response = query_inference(&service, &cfg.model, "hi", 5);
+        unsafe {
+            // SAFETY: see above.
+            std::env::remove_var("FUSION_TEST_API_KEY");
+        }
+        assert_eq!(response.unwrap(), "ok");
+
+        let head: Vec<String> =
+            stub.join().unwrap().iter().map(|line| line.to_ascii_lowercase()).collect();
+        for expected in [
+            "authorization: bearer s3cret",
+            "accept: application/json",
+            "content-type: application/json",
+        ] {
+            assert!(head.iter().any(|line| line == expected), "missing {expected}: {head:?}");
+        }
+
+        let err = query_inference(&service, &cfg.model, "hi", 5).unwrap_err();
+        assert!(err.to_string().contains("FUSION_TEST_API_KEY"), "got {err}");
+    }
+
+    #[test]
+    fn port_probe_accepts_a_listener_that_inference_rejects() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let cfg = MlxServerConfig {
+            port: listener.local_addr().unwrap().port(),
+            ..MlxServerConfig::default()
+        };
+        let service = services::create_mlx_service(&cfg);
+        let stub = thread::spawn(move || {
+            // The port probe only connects; the inference probe then gets a 404.
+            drop(listener.accept().unwrap());
+            respond(&listener, "404 Not Found", "no such route")
+        });
+
+        assert_eq!(check_readiness(&service, &cfg.model, ReadinessProbe::Port, 2), Ok(()));
+        assert_eq!(
+            check_readiness(&service, &cfg.model, ReadinessProbe::Inference, 2),
+            Err(ReadinessError::NotReady { status: 404 })
+        );
+        stub.join().unwrap();
+
+        let port = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+        let down = services::create_mlx_service(&MlxServerConfig { port, ..cfg });
+        let result = check_readiness(&down, "m", ReadinessProbe::Port, 1);
+        assert!(matches!(result, Err(ReadinessError::Unreachable(_))), "got {result:?}");
+    }
+
+    #[test]
+    fn readiness_uses_configured_get_path() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let cfg = MlxServerConfig {
+            port: listener.local_addr().unwrap().port(),
+            ready_path: Some("/health".into()),
+            ready_method: Some(ReadyMethod::Get),
+            ..MlxServerConfig::default()
+        };
+        let service = services::create_mlx_service(&cfg);
+        let stub = thread::spawn(move || respond(&listener, "200 OK", "{}"));
+
+        assert_eq!(check_inference_readiness(&service, &cfg.model, 2), Ok(()));
+        assert_eq!(stub.join().unwrap(), "GET /health HTTP/1.1");
+    }
+
+    #[test]
+    fn readiness_posts_to_default_chat_path_when_only_method_is_set() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let cfg = MlxServerConfig {
+            port: listener.local_addr().unwrap().port(),
+            ready_method: Some(ReadyMethod::Post),
+            ..MlxServerConfig::default()
+        };
+        let service = services::create_mlx_service(&cfg);
+        let stub = thread::spawn(move || respond(&listener, "503 Service Unavailable", "{}"));
+
+        assert_eq!(
+            check_inference_readiness(&service, &cfg.model, 2),
+            Err(ReadinessError::NotReady { status: 503 })
+        );
+        assert_eq!(stub.join().unwrap(), "POST /v1/chat/completions HTTP/1.1");
+    }
+
+    #[test]
+    fn ollama_health_falls_back_to_native_chat_on_404() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let cfg = OllamaServerConfig {
+            port: listener.local_addr().unwrap().port(),
+            ..OllamaServerConfig::default()
+        };
+        let service = services::create_ollama_service(&cfg);
+
+        let stub = thread::spawn(move || {
+            let mut requests = Vec::new();
+            for _ in 0..2 {
+                requests.push(respond(&listener, "404 Not Found", "404 page not found"));
+                requests.push(respond(
+                    &listener,
+                    "200 OK",
+                    r#"{"message":{"role":"assistant","content":"native"}}"#,
+                ));
+            }
+            requests
+        });
+
+        let response = query_inference(&service, &cfg.model, "hi", 5).expect("fallback works");
+        assert_eq!(response, "native");
+        assert_eq!(check_inference_readiness(&service, &cfg.model, 5), Ok(()));
+
+        let requests = stub.join().unwrap();
+        assert_eq!(
+            requests,
+            [
+                "POST /v1/chat/completions HTTP/1.1",
+                "POST /api/chat HTTP/1.1",
+                "POST /v1/chat/completions HTTP/1.1",
+                "POST /api/chat HTTP/1.1",
+            ]
+        );
+    }
+
+    #[test]
+    fn ping_reports_latency_without_inference() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let cfg = MlxServerConfig {
+            port: listener.local_addr().unwrap().port(),
+            ..MlxServerConfig::default()
+        };
+        let service = services::create_mlx_service(&cfg);
+        let stub = thread::spawn(move || respond(&listener, "404 Not Found", "not found"));
+
+        let latency = ping(&service, Duration::from_secs(2)).expect("ping should succeed");
+        assert!(latency < Duration::from_secs(2));
+        assert_eq!(stub.join().unwrap(), "GET / HTTP/1.1");
+
+        let port = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+        let down = services::create_mlx_service(&MlxServerConfig { port, ..cfg });
+        assert!(ping(&down, Duration::from_secs(1)).is_err());
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn debug_http_logs_inference_exchange() {
+        let _project = TestProject::new();
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let cfg = MlxServerConfig {
+            port: listener.local_addr().unwrap().port(),
+            ..MlxServerConfig::default()
+        };
+        let service = services::create_mlx_service(&cfg);
+        let stub = thread::spawn(move || {
+            respond(&listener, "200 OK", r#"{"choices":[{"message":{"content":"logged"}}]}"#)
+        });
+
+        http_log::set_enabled(true);
+        let response = query_inference(&service, &cfg.model, "hi", 5);
+        http_log::set_enabled(false);
+        assert_eq!(response.unwrap(), "logged");
+        stub.join().unwrap();
+
+        let log = std::fs::read_to_string(http_log::log_path().unwrap()).unwrap();
+        assert!(log.contains(&cfg.model), "log: {log}");
+        assert!(log.contains(r#""content":"logged""#), "log: {log}");
     }
 }
@@ -0,0 +1,227 @@
+//! Shared HTTP client settings, so every request to a runtime uses the same connect timeout and
+//! retry policy.
+//!
+//! Precedence: global CLI flags (`--timeout-connect`, `--retries`), then `FUSION_CONNECT_TIMEOUT_SECS`
+//! and `FUSION_HTTP_RETRIES`, then the defaults below.
+
+use crate::core::config;
+use crate::core::services::ManagedService;
+use crate::error::AppError;
+use reqwest::blocking::{Client, Response};
+use reqwest::header::{ACCEPT, HeaderMap, HeaderName, HeaderValue};
+use std::env;
+use std::fmt::Display;
+use std::sync::RwLock;
+use std::thread;
+use std::time::Duration;
+
+pub const DEFAULT_CONNECT_TIMEOUT_SECS: u64 = 10;
+/// Retries after the first attempt, so three attempts in total by default.
+pub const DEFAULT_RETRIES: u32 = 2;
+const RETRY_INITIAL_BACKOFF_MS: u64 = 100;
+const RETRY_MAX_BACKOFF_MS: u64 = 1000;
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Overrides {
+    connect_timeout_secs: Option<u64>,
+    retries: Option<u32>,
+}
+
+static CLI_OVERRIDES: RwLock<Overrides> =
+    RwLock::new(Overrides { connect_timeout_secs: None, retries: None });
+
+/// Apply the global `--timeout-connect` and `--retries` flags for the rest of the process.
+pub fn set_overrides(connect_timeout_secs: Option<u64>, retries: Option<u32>) {
+    let mut guard = CLI_OVERRIDES.write().unwrap_or_else(|poisoned| poisoned.into_inner());
+    *guard = Overrides { connect_timeout_secs, retries };
+}
+
+/// Connection behaviour applied to every HTTP call fusion makes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HttpSettings {
+    pub connect_timeout: Duration,
+    pub retries: u32,
+}
+
+impl HttpSettings {
+    pub fn current() -> Self {
+        let overrides = *CLI_OVERRIDES.read().unwrap_or_else(|poisoned| poisoned.into_inner());
+        Self::resolve(overrides, |key| env::var(key).ok())
+    }
+
+    fn resolve(overrides: Overrides, lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let from_env = |key: &str| lookup(key).and_then(|value| value.trim().parse::<u64>().ok());
+        let connect_timeout_secs = overrides
+            .connect_timeout_secs
+            .or_else(|| from_env("FUSION_CONNECT_TIMEOUT_SECS"))
+            .filter(|secs| *secs > 0)
+            .unwrap_or(DEFAULT_CONNECT_TIMEOUT_SECS);
+        let retries = overrides
+            .retries
+            .or_else(|| from_env("FUSION_HTTP_RETRIES").and_then(|n| u32::try_from(n).ok()))
+            .unwrap_or(DEFAULT_RETRIES);
+        Self { connect_timeout: Duration::from_secs(connect_timeout_secs), retries }
+    }
+}
+
+/// Build a client with the shared connect timeout; `timeout` bounds each whole request, and
+/// `None` leaves long-running streams unbounded.
+pub fn client(timeout: Option<Duration>) -> reqwest::Result<Client> {
+    Client::builder()
+        .connect_timeout(HttpSettings::current().connect_timeout)
+        .timeout(timeout)
+        .build()
+}
+
+/// Client for talking to `service`: the shared settings plus `Accept: application/json` and the
+/// service's configured headers.
+pub fn client_for(service: &ManagedService, timeout: Option<Duration>) -> Result<Client, AppError> {
+    Client::builder()
+        .connect_timeout(HttpSettings::current().connect_timeout)
+        .timeout(timeout)
+        .default_headers(service_headers(service)?)
+        .build()
+        .map_err(|e| AppError::process_error(service.name, format!("Client build error: {e}")))
+}
+
+/// The service's configured headers with `${VAR}` references expanded.
+pub fn service_headers(service: &ManagedService) -> Result<HeaderMap, AppError> {
+    let mut headers = HeaderMap::new();
+    headers.insert(ACCEPT, HeaderValue::from_static("application/json"));
+    for (name, value) in &service.headers {
+        let invalid = |detail: String| {
+            AppError::config_error(format!(
+                "invalid header '{name}' for {}: {detail}",
+                service.name
+            ))
+        };
+        let name = HeaderName::from_bytes(name.trim().as_bytes())
+            .map_err(|err| invalid(err.to_string()))?;
+        let value = HeaderValue::from_str(&config::expand_env_vars(value)?)
+            .map_err(|err| invalid(err.to_string()))?;
+        headers.insert(name, value);
+    }
+    Ok(headers)
+}
+
+/// Send a request, retrying only failures to connect, with exponential backoff.
+///
+/// A connect failure means the request never left, so this is safe for every method. HTTP error
+/// statuses are returned as responses and never retried, and the last error is returned once
+/// retries run out.
+pub fn send_with_retry(
+    send: impl FnMut() -> reqwest::Result<Response>,
+) -> reqwest::Result<Response> {
+    retry(HttpSettings::current().retries, reqwest::Error::is_connect, send)
+}
+
+/// [`send_with_retry`] that also retries requests that failed after connecting, such as a reset
+/// connection, except timeouts. Only for idempotent calls like health probes, since the server
+/// may already have acted on the failed attempt.
+pub fn send_idempotent_with_retry(
+    send: impl FnMut() -> reqwest::Result<Response>,
+) -> reqwest::Result<Response> {
+    retry(HttpSettings::current().retries, is_transient, send)
+}
+
+fn is_transient(err: &reqwest::Error) -> bool {
+    err.is_connect() || (err.is_request() && !err.is_timeout())
+}
+
+/// Call `send` up to `retries` more times while it fails with an error `retryable` accepts.
+fn retry<T, E: Display>(
+    retries: u32,
+    retryable: impl Fn(&E) -> bool,
+    mut send: impl FnMut() -> Result<T, E>,
+) -> Result<T, E> {
+    let mut backoff = Duration::from_millis(RETRY_INITIAL_BACKOFF_MS);
+    let mut attempt = 1;
+    loop {
+        tracing::debug!(attempt, "sending HTTP request");
+        match send() {
+            Err(err) if attempt <= retries && retryable(&err) => {
+                tracing::debug!(attempt, error = %err, backoff_ms = backoff.as_millis() as u64, "retrying");
+                thread::sleep(backoff);
+                backoff = (backoff * 2).min(Duration::from_millis(RETRY_MAX_BACKOFF_MS));
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolve(overrides: Overrides, vars: &[(&str, &str)]) -> HttpSettings {
+        HttpSettings::resolve(overrides, |key| {
+            vars.iter().find(|(name, _)| *name == key).map(|(_, value)| value.to_string())
+        })
+    }
+
+    #[test]
+    fn defaults_apply_without_overrides() {
+        let settings = resolve(Overrides::default(), &[]);
+        assert_eq!(settings.connect_timeout, Duration::from_secs(DEFAULT_CONNECT_TIMEOUT_SECS));
+        assert_eq!(settings.retries, DEFAULT_RETRIES);
+
+        let invalid = [("FUSION_CONNECT_TIMEOUT_SECS", "soon"), ("FUSION_HTTP_RETRIES", "-1")];
+        assert_eq!(resolve(Overrides::default(), &invalid), settings);
+        let zero = [("FUSION_CONNECT_TIMEOUT_SECS", "0")];
+        assert_eq!(resolve(Overrides::default(), &zero), settings);
+    }
+
+    #[test]
+    fn env_overrides_defaults_and_flags_override_env() {
+        let vars = [("FUSION_CONNECT_TIMEOUT_SECS", "3"), ("FUSION_HTTP_RETRIES", "0")];
+        let settings = resolve(Overrides::default(), &vars);
+        assert_eq!(settings, HttpSettings { connect_timeout: Duration::from_secs(3), retries: 0 });
+
+        let flags = Overrides { connect_timeout_secs: Some(7), retries: Some(5) };
+        let settings = resolve(flags, &vars);
+        assert_eq!(settings, HttpSettings { connect_timeout: Duration::from_secs(7), retries: 5 });
+    }
+
+    #[test]
+    fn retry_stops_at_the_first_non_retryable_result() {
+        let mut attempts = 0;
+        let result: Result<(), &str> = retry(
+            5,
+            |err| *err == "refused",
+            || {
+                attempts += 1;
+                if attempts < 2 { Err("refused") } else { Err("reset") }
+            },
+        );
+        assert_eq!(result, Err("reset"));
+        assert_eq!(attempts, 2);
+    }
+
+    #[test]
+    fn retry_gives_up_after_the_configured_retries() {
+        let mut attempts = 0;
+        let result: Result<(), &str> = retry(
+            2,
+            |_| true,
+            || {
+                attempts += 1;
+                Err("refused")
+            },
+        );
+        assert_eq!(result, Err("refused"));
+        assert_eq!(attempts, 3);
+
+        attempts = 0;
+        let result: Result<u32, &str> = retry(
+            0,
+            |_| true,
+            || {
+                attempts += 1;
+                Ok(7)
+            },
+        );
+        assert_eq!(result, Ok(7));
+        assert_eq!(attempts, 1);
+    }
+}
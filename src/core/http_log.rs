@@ -0,0 +1,118 @@
+//! Opt-in transcript of HTTP exchanges with managed services for debugging odd inference results.
+//!
+//! Prompts can be sensitive, so nothing is written unless `--debug-http` or `FUSION_HTTP_DEBUG=1`
+//! is set.
+
+use crate::core::paths;
+use crate::error::AppError;
+use std::env;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Turn logging on for the rest of the process (`fusion --debug-http`).
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed) || env::var("FUSION_HTTP_DEBUG").is_ok_and(|value| value == "1")
+}
+
+/// Location of the transcript: `requests.log` in the runtime state directory.
+pub fn log_path() -> Result<PathBuf, AppError> {
+    Ok(paths::pid_dir()?.join("requests.log"))
+}
+
+/// Append one request/response pair when logging is enabled; `request` is `None` for calls
+/// without a body. Failures only produce a warning, since the transcript must never break the
+/// command being debugged.
+pub fn record(
+    service: &str,
+    method: &str,
+    url: &str,
+    request: Option<&serde_json::Value>,
+    status: u16,
+    response: &str,
+) {
+    if !is_enabled() {
+        return;
+    }
+    if let Err(err) = append(service, method, url, request, status, response) {
+        tracing::warn!(error = %err, "failed to write HTTP debug log");
+    }
+}
+
+fn append(
+    service: &str,
+    method: &str,
+    url: &str,
+    request: Option<&serde_json::Value>,
+    status: u16,
+    response: &str,
+) -> Result<(), AppError> {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+    let path = log_path()?;
+    paths::ensure_pid_dir()?;
+    let mut file = open_private(&path)?;
+    writeln!(file, "[{}.{:03}] {service} {method} {url}", now.as_secs(), now.subsec_millis())?;
+    if let Some(request) = request {
+        writeln!(file, "> {request}")?;
+    }
+    writeln!(file, "< {status} {}", response.trim_end())?;
+    writeln!(file)?;
+    Ok(())
+}
+
+/// Open the transcript for appending, creating it readable by the owner only since it holds
+/// prompts and responses.
+fn open_private(path: &Path) -> std::io::Result<File> {
+    let mut options = OpenOptions::new();
+    options.create(true).append(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options.open(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_support::TestProject;
+    use std::fs;
+
+    #[test]
+    #[serial_test::serial]
+    fn nothing_is_written_while_disabled() {
+        let _project = TestProject::new();
+        set_enabled(false);
+        let request = serde_json::json!({"model": "m"});
+        record("mlx", "POST", "http://127.0.0.1:8080/", Some(&request), 200, "{}");
+        assert!(!log_path().unwrap().exists());
+
+        set_enabled(true);
+        record("mlx", "POST", "http://127.0.0.1:8080/", Some(&request), 200, "{}");
+        record("mlx", "GET", "http://127.0.0.1:8080/v1/models", None, 200, "{}");
+        set_enabled(false);
+        let contents = fs::read_to_string(log_path().unwrap()).unwrap();
+        assert!(contents.contains(r#"> {"model":"m"}"#), "log: {contents}");
+        assert!(contents.contains("< 200 {}"), "log: {contents}");
+        assert!(
+            contents.contains("mlx GET http://127.0.0.1:8080/v1/models\n< 200"),
+            "log: {contents}"
+        );
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(log_path().unwrap()).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+    }
+}
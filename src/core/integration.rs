@@ -0,0 +1,209 @@
+//! Renderers for OS service-manager definitions (systemd, launchd) that keep a runtime alive.
+
+use crate::core::services::ManagedService;
+use std::env;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+/// Render a `systemd --user` unit that runs the service command with its environment.
+pub fn render_systemd_unit(service: &ManagedService) -> String {
+    let mut unit = String::new();
+    let _ = writeln!(unit, "[Unit]");
+    let _ = writeln!(unit, "Description=Fusion managed {} runtime", service.name);
+    let _ = writeln!(unit, "After=network.target");
+    let _ = writeln!(unit);
+    let _ = writeln!(unit, "[Service]");
+    let _ = writeln!(unit, "Type=simple");
+    let _ = writeln!(unit, "ExecStart={}", systemd_exec_line(&resolved_command(service)));
+    for (key, value) in sorted_env(service) {
+        let _ = writeln!(unit, "Environment={}", systemd_env_quote(&format!("{key}={value}")));
+    }
+    let _ = writeln!(unit, "Restart=on-failure");
+    let _ = writeln!(unit, "RestartSec=5");
+    let _ = writeln!(unit);
+    let _ = writeln!(unit, "[Install]");
+    let _ = writeln!(unit, "WantedBy=default.target");
+    unit
+}
+
+/// Name of the unit file generated for a service, e.g. `fusion-ollama.service`.
+pub fn systemd_unit_name(service: &ManagedService) -> String {
+    format!("fusion-{}.service", service.name)
+}
+
+/// Render a launchd agent plist that keeps the service command alive for the current user.
+pub fn render_launchd_plist(service: &ManagedService) -> String {
+    let mut plist = String::new();
+    let _ = writeln!(plist, r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    let _ = writeln!(
+        plist,
+        r#"<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">"#
+    );
+    let _ = writeln!(plist, r#"<plist version="1.0">"#);
+    let _ = writeln!(plist, "<dict>");
+    let _ = writeln!(plist, "    <key>Label</key>");
+    let _ = writeln!(plist, "    <string>{}</string>", xml_escape(&launchd_label(service)));
+    let _ = writeln!(plist, "    <key>ProgramArguments</key>");
+    let _ = writeln!(plist, "    <array>");
+    for arg in resolved_command(service) {
+        let _ = writeln!(plist, "        <string>{}</string>", xml_escape(&arg));
+    }
+    let _ = writeln!(plist, "    </array>");
+    if !service.env.is_empty() {
+        let _ = writeln!(plist, "    <key>EnvironmentVariables</key>");
+        let _ = writeln!(plist, "    <dict>");
+        for (key, value) in sorted_env(service) {
+            let _ = writeln!(plist, "        <key>{}</key>", xml_escape(key));
+            let _ = writeln!(plist, "        <string>{}</string>", xml_escape(value));
+        }
+        let _ = writeln!(plist, "    </dict>");
+    }
+    if let (Ok(log_path), Ok(stderr_path)) = (service.log_path(), service.stderr_log_path()) {
+        let log_path = xml_escape(&log_path.display().to_string());
+        let stderr_path = xml_escape(&stderr_path.display().to_string());
+        let _ = writeln!(plist, "    <key>StandardOutPath</key>");
+        let _ = writeln!(plist, "    <string>{log_path}</string>");
+        let _ = writeln!(plist, "    <key>StandardErrorPath</key>");
+        let _ = writeln!(plist, "    <string>{stderr_path}</string>");
+    }
+    let _ = writeln!(plist, "    <key>RunAtLoad</key>");
+    let _ = writeln!(plist, "    <true/>");
+    let _ = writeln!(plist, "    <key>KeepAlive</key>");
+    let _ = writeln!(plist, "    <true/>");
+    let _ = writeln!(plist, "</dict>");
+    let _ = writeln!(plist, "</plist>");
+    plist
+}
+
+/// launchd label for a service, e.g. `com.fusion.mlx`.
+pub fn launchd_label(service: &ManagedService) -> String {
+    format!("com.fusion.{}", service.name)
+}
+
+/// Return the service command with its executable resolved against `PATH` when possible.
+pub fn resolved_command(service: &ManagedService) -> Vec<String> {
+    let mut command = service.command.clone();
+    if let Some(program) = command.first_mut()
+        && let Some(resolved) = resolve_executable(program)
+    {
+        *program = resolved.display().to_string();
+    }
+    command
+}
+
+pub(crate) fn resolve_executable(program: &str) -> Option<PathBuf> {
+    let candidate = Path::new(program);
+    if candidate.components().count() > 1 {
+        return Some(candidate.to_path_buf());
+    }
+    env::var_os("PATH").and_then(|paths| {
+        env::split_paths(&paths).map(|dir| dir.join(program)).find(|path| path.is_file())
+    })
+}
+
+fn sorted_env(service: &ManagedService) -> Vec<(&String, &String)> {
+    let mut env: Vec<_> = service.env.iter().collect();
+    env.sort();
+    env
+}
+
+fn systemd_exec_line(command: &[String]) -> String {
+    command.iter().map(|arg| systemd_quote(arg)).collect::<Vec<_>>().join(" ")
+}
+
+/// Quote an `ExecStart=` word for systemd unit syntax, escaping characters the parser treats
+/// specially; `%` specifiers and `$VAR` expansion are doubled so the value reaches the server
+/// unchanged.
+fn systemd_quote(value: &str) -> String {
+    quote_unit_value(value, true)
+}
+
+/// Quote an `Environment=` assignment. systemd expands `%` specifiers there but not `$VAR`, so a
+/// `$` is kept as written.
+fn systemd_env_quote(value: &str) -> String {
+    quote_unit_value(value, false)
+}
+
+fn quote_unit_value(value: &str, escape_dollar: bool) -> String {
+    let needs_quotes = value.is_empty()
+        || value.chars().any(|c| {
+            c.is_whitespace() || matches!(c, '"' | '\'' | '\\' | ';') || (escape_dollar && c == '$')
+        });
+    let mut escaped = value.replace('\\', "\\\\").replace('"', "\\\"").replace('%', "%%");
+    if escape_dollar {
+        escaped = escaped.replace('$', "$$");
+    }
+    if needs_quotes { format!("\"{escaped}\"") } else { escaped }
+}
+
+fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::MlxServerConfig;
+    use crate::core::services;
+    use crate::core::test_support::TestProject;
+
+    #[test]
+    fn systemd_unit_contains_model_port_and_restart_policy() {
+        let cfg = MlxServerConfig { port: 5050, ..MlxServerConfig::default() };
+        let mut service = services::create_mlx_service(&cfg);
+        service.env.insert("MLX_EXTRA".into(), "two words".into());
+
+        let unit = render_systemd_unit(&service);
+        assert!(unit.contains(&cfg.model));
+        assert!(unit.contains("--port 5050"));
+        assert!(unit.contains("Restart=on-failure"));
+        assert!(unit.contains("Environment=\"MLX_EXTRA=two words\""));
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn launchd_plist_lists_program_arguments() {
+        let _project = TestProject::new();
+        let cfg = MlxServerConfig::default();
+        let mut service = services::create_mlx_service(&cfg);
+        service.env.insert("MLX_TOKEN".into(), "a<b".into());
+
+        let plist = render_launchd_plist(&service);
+        let arguments = plist
+            .split_once("<key>ProgramArguments</key>")
+            .and_then(|(_, rest)| rest.split_once("</array>"))
+            .map(|(array, _)| {
+                array
+                    .lines()
+                    .filter_map(|line| line.trim().strip_prefix("<string>"))
+                    .filter_map(|line| line.strip_suffix("</string>"))
+                    .map(str::to_string)
+                    .collect::<Vec<_>>()
+            })
+            .expect("plist should contain a ProgramArguments array");
+
+        assert_eq!(arguments.len(), service.command.len());
+        assert!(arguments[0].ends_with("mlx_lm.server"));
+        assert_eq!(arguments[1..], service.command[1..]);
+        assert!(arguments.contains(&cfg.model));
+        assert!(plist.contains("<string>com.fusion.mlx</string>"));
+        assert!(plist.contains("<string>a&lt;b</string>"));
+        assert!(plist.contains("<key>KeepAlive</key>"));
+    }
+
+    #[test]
+    fn systemd_quote_escapes_special_characters() {
+        assert_eq!(systemd_quote("plain"), "plain");
+        assert_eq!(systemd_quote("a b"), "\"a b\"");
+        assert_eq!(systemd_quote("say \"hi\""), "\"say \\\"hi\\\"\"");
+        assert_eq!(systemd_quote("100%"), "100%%");
+        assert_eq!(systemd_quote("$HOME/models"), "\"$$HOME/models\"");
+        assert_eq!(systemd_quote("${MLX_KEY}"), "\"$${MLX_KEY}\"");
+        assert_eq!(systemd_env_quote("KEY=$HOME"), "KEY=$HOME");
+    }
+}
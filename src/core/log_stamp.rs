@@ -0,0 +1,282 @@
+//! Timestamped server logs. Runtimes rarely stamp their own output, so `up` relays it through a
+//! helper process that prefixes every line with the UTC time, which `log --since` filters on.
+
+use crate::error::AppError;
+use std::env;
+use std::fs::OpenOptions;
+use std::io::{self, Read, Write};
+use std::path::Path;
+use std::process::Command;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Hidden `fusion` subcommand that copies stdin into a log file, stamping each line.
+pub const STAMP_SUBCOMMAND: &str = "__stamp-log";
+
+const SECS_PER_DAY: u64 = 86_400;
+
+/// Writer that prefixes each line with `YYYY-MM-DDTHH:MM:SSZ `.
+pub struct StampingWriter<W: Write> {
+    inner: W,
+    at_line_start: bool,
+    clock: fn() -> u64,
+}
+
+impl<W: Write> StampingWriter<W> {
+    pub fn new(inner: W) -> Self {
+        Self { inner, at_line_start: true, clock: now_secs }
+    }
+}
+
+impl<W: Write> Write for StampingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        for chunk in buf.split_inclusive(|byte| *byte == b'\n') {
+            if self.at_line_start {
+                write!(self.inner, "{} ", format_timestamp((self.clock)()))?;
+            }
+            self.inner.write_all(chunk)?;
+            self.at_line_start = chunk.ends_with(b"\n");
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Command that runs this executable as a stamping relay into `log_path`, or `None` when the
+/// current executable is not the `fusion` binary (e.g. under `cargo test`).
+pub fn stamper_command(log_path: &Path) -> Option<Command> {
+    let exe = env::current_exe().ok()?;
+    if exe.file_stem()? != env!("CARGO_PKG_NAME") {
+        return None;
+    }
+    let mut command = Command::new(exe);
+    command.arg(STAMP_SUBCOMMAND).arg(log_path);
+    Some(command)
+}
+
+/// Body of the hidden stamping subcommand: append stdin to `log_path` until EOF.
+pub fn relay_to_file(input: impl Read, log_path: &Path) -> Result<(), AppError> {
+    let file = OpenOptions::new().create(true).append(true).open(log_path)?;
+    relay(input, file)
+}
+
+fn relay(mut input: impl Read, output: impl Write) -> Result<(), AppError> {
+    let mut writer = StampingWriter::new(output);
+    io::copy(&mut input, &mut writer)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Lines stamped at or after `cutoff` (UNIX seconds). Unstamped lines, such as the rest of a
+/// multi-line message, follow the decision made for the stamped line before them.
+pub fn lines_since(contents: &str, cutoff: u64) -> Vec<&str> {
+    let mut keep = false;
+    contents
+        .lines()
+        .filter(|line| {
+            if let Some(stamp) = line.split_once(' ').and_then(|(head, _)| parse_timestamp(head)) {
+                keep = stamp >= cutoff;
+            }
+            keep
+        })
+        .collect()
+}
+
+/// Interleave two stamped logs by time, keeping each multi-line entry together. Equal stamps
+/// keep `first` ahead, and unstamped leading lines (e.g. a legacy log) sort before everything.
+pub fn merge_by_time<'a>(first: &'a str, second: &'a str) -> Vec<&'a str> {
+    let (first, second) = (stamped_entries(first), stamped_entries(second));
+    let mut merged = Vec::new();
+    let (mut left, mut right) = (first.iter().peekable(), second.iter().peekable());
+    loop {
+        let next = match (left.peek(), right.peek()) {
+            (Some(a), Some(b)) if a.0 <= b.0 => left.next(),
+            (Some(_), Some(_)) => right.next(),
+            (Some(_), None) => left.next(),
+            (None, _) => right.next(),
+        };
+        match next {
+            Some((_, lines)) => merged.extend(lines),
+            None => return merged,
+        }
+    }
+}
+
+/// Split a log into entries: a stamped line plus any unstamped lines that follow it.
+fn stamped_entries(contents: &str) -> Vec<(u64, Vec<&str>)> {
+    let mut entries: Vec<(u64, Vec<&str>)> = Vec::new();
+    for line in contents.lines() {
+        let stamp = line.split_once(' ').and_then(|(head, _)| parse_timestamp(head));
+        match (stamp, entries.last_mut()) {
+            (Some(stamp), _) => entries.push((stamp, vec![line])),
+            (None, Some((_, lines))) => lines.push(line),
+            (None, None) => entries.push((0, vec![line])),
+        }
+    }
+    entries
+}
+
+/// UNIX seconds `window` ago, for use with [`lines_since`].
+pub fn cutoff_for(window: Duration) -> u64 {
+    now_secs().saturating_sub(window.as_secs())
+}
+
+/// Parse a `--since` window such as `90s`, `10m`, `2h`, or `1d`; a bare number means seconds.
+pub fn parse_since(raw: &str) -> Result<Duration, String> {
+    let raw = raw.trim();
+    let split = raw.find(|c: char| !c.is_ascii_digit()).unwrap_or(raw.len());
+    let (digits, unit) = raw.split_at(split);
+    let value: u64 =
+        digits.parse().map_err(|_| format!("invalid duration '{raw}' (expected e.g. 10m)"))?;
+    let scale = match unit {
+        "" | "s" => 1,
+        "m" => 60,
+        "h" => 3_600,
+        "d" => SECS_PER_DAY,
+        _ => return Err(format!("unknown unit '{unit}' in '{raw}' (use s, m, h, or d)")),
+    };
+    Ok(Duration::from_secs(value.saturating_mul(scale)))
+}
+
+/// The current UTC time in the log stamp format.
+pub fn now_timestamp() -> String {
+    format_timestamp(now_secs())
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|elapsed| elapsed.as_secs()).unwrap_or(0)
+}
+
+pub fn format_timestamp(secs: u64) -> String {
+    let (year, month, day) = civil_from_days((secs / SECS_PER_DAY) as i64);
+    let time = secs % SECS_PER_DAY;
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        time / 3_600,
+        time % 3_600 / 60,
+        time % 60
+    )
+}
+
+pub fn parse_timestamp(raw: &str) -> Option<u64> {
+    let bytes = raw.as_bytes();
+    if bytes.len() != 20
+        || bytes[4] != b'-'
+        || bytes[7] != b'-'
+        || bytes[10] != b'T'
+        || bytes[13] != b':'
+        || bytes[16] != b':'
+        || bytes[19] != b'Z'
+    {
+        return None;
+    }
+    let field = |range: std::ops::Range<usize>| raw.get(range)?.parse::<u64>().ok();
+    let (year, month, day) = (field(0..4)?, field(5..7)?, field(8..10)?);
+    let (hour, minute, second) = (field(11..13)?, field(14..16)?, field(17..19)?);
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) || hour > 23 || minute > 59 {
+        return None;
+    }
+    let days = u64::try_from(days_from_civil(year as i64, month, day)).ok()?;
+    Some(days * SECS_PER_DAY + hour * 3_600 + minute * 60 + second.min(60))
+}
+
+// Proleptic Gregorian conversions from Howard Hinnant's `chrono`-compatible date algorithms.
+fn civil_from_days(days: i64) -> (i64, u64, u64) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u64;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u64;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+fn days_from_civil(year: i64, month: u64, day: u64) -> i64 {
+    let year = year - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let mp = if month > 2 { month - 3 } else { month + 9 } as i64;
+    let doy = (153 * mp + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timestamps_round_trip() {
+        assert_eq!(format_timestamp(0), "1970-01-01T00:00:00Z");
+        assert_eq!(format_timestamp(1_709_210_096), "2024-02-29T12:34:56Z");
+        assert_eq!(parse_timestamp("2024-02-29T12:34:56Z"), Some(1_709_210_096));
+        assert_eq!(parse_timestamp("2024-02-29 12:34:56"), None);
+        assert_eq!(parse_timestamp("level=info"), None);
+    }
+
+    #[test]
+    fn writer_stamps_each_line_once() {
+        let mut output = Vec::new();
+        let mut writer = StampingWriter { inner: &mut output, at_line_start: true, clock: || 0 };
+        writer.write_all(b"first\nsec").unwrap();
+        writer.write_all(b"ond\nthird\n").unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "1970-01-01T00:00:00Z first\n\
+             1970-01-01T00:00:00Z second\n\
+             1970-01-01T00:00:00Z third\n"
+        );
+    }
+
+    #[test]
+    fn lines_since_filters_synthetic_log() {
+        let log = "2024-02-29T12:00:00Z loading model\n\
+                   2024-02-29T12:20:00Z request failed\n\
+                   \ttraceback line\n\
+                   2024-02-29T12:30:00Z ready\n";
+        let cutoff = parse_timestamp("2024-02-29T12:10:00Z").unwrap();
+        assert_eq!(
+            lines_since(log, cutoff),
+            [
+                "2024-02-29T12:20:00Z request failed",
+                "\ttraceback line",
+                "2024-02-29T12:30:00Z ready"
+            ]
+        );
+        assert!(lines_since("unstamped legacy output\n", 0).is_empty());
+    }
+
+    #[test]
+    fn merge_interleaves_streams_by_stamp() {
+        let stdout = "2024-02-29T12:00:00Z starting\n\
+                      2024-02-29T12:00:05Z listening\n";
+        let stderr = "2024-02-29T12:00:01Z warning: slow disk\n\
+                      \tdetail\n\
+                      2024-02-29T12:00:05Z loaded\n";
+        assert_eq!(
+            merge_by_time(stdout, stderr),
+            [
+                "2024-02-29T12:00:00Z starting",
+                "2024-02-29T12:00:01Z warning: slow disk",
+                "\tdetail",
+                "2024-02-29T12:00:05Z listening",
+                "2024-02-29T12:00:05Z loaded",
+            ]
+        );
+        assert_eq!(merge_by_time("legacy\n", ""), ["legacy"]);
+    }
+
+    #[test]
+    fn since_windows_accept_common_units() {
+        assert_eq!(parse_since("10m"), Ok(Duration::from_secs(600)));
+        assert_eq!(parse_since("90"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse_since("1d"), Ok(Duration::from_secs(86_400)));
+        assert!(parse_since("10w").is_err());
+        assert!(parse_since("m").is_err());
+    }
+}
@@ -0,0 +1,134 @@
+//! Prometheus text-format exposition of managed service status and resource usage.
+
+use crate::core::process::{self, ResourceUsage, StatusOutcome};
+use crate::core::services::ManagedService;
+use std::fmt::Write;
+
+struct ServiceSample {
+    name: &'static str,
+    pid: Option<i32>,
+    usage: Option<ResourceUsage>,
+}
+
+/// Render a Prometheus exposition snapshot suitable for node_exporter's textfile collector.
+pub fn render_prometheus(services: &[ManagedService]) -> String {
+    let samples: Vec<ServiceSample> = services.iter().map(sample).collect();
+    let mut out = String::new();
+
+    write_header(&mut out, "fusion_service_up", "Whether the managed service is running.");
+    for sample in &samples {
+        let up = u8::from(sample.pid.is_some());
+        let _ = writeln!(out, "fusion_service_up{{service=\"{}\"}} {up}", sample.name);
+    }
+
+    write_header(&mut out, "fusion_service_pid", "Process ID of the running service.");
+    for sample in &samples {
+        if let Some(pid) = sample.pid {
+            let _ = writeln!(out, "fusion_service_pid{{service=\"{}\"}} {pid}", sample.name);
+        }
+    }
+
+    write_header(&mut out, "fusion_service_cpu_percent", "CPU usage of the service process.");
+    for sample in &samples {
+        if let Some(usage) = sample.usage {
+            let _ = writeln!(
+                out,
+                "fusion_service_cpu_percent{{service=\"{}\"}} {}",
+                sample.name, usage.cpu_percent
+            );
+        }
+    }
+
+    write_header(
+        &mut out,
+        "fusion_service_memory_bytes",
+        "Resident memory of the service process.",
+    );
+    for sample in &samples {
+        if let Some(usage) = sample.usage {
+            let _ = writeln!(
+                out,
+                "fusion_service_memory_bytes{{service=\"{}\"}} {}",
+                sample.name, usage.memory_bytes
+            );
+        }
+    }
+
+    out
+}
+
+fn sample(service: &ManagedService) -> ServiceSample {
+    let pid = match process::status_service(service) {
+        Ok(StatusOutcome::Running { pid }) => Some(pid),
+        Ok(StatusOutcome::NotRunning) | Err(_) => None,
+    };
+    let usage = pid.and_then(|pid| process::resource_usage(service, pid));
+    ServiceSample { name: service.name, pid, usage }
+}
+
+fn write_header(out: &mut String, metric: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {metric} {help}");
+    let _ = writeln!(out, "# TYPE {metric} gauge");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::Config;
+    use crate::core::process::{ProcessDriver, install_driver};
+    use crate::core::services;
+    use crate::core::test_support::TestProject;
+    use crate::error::AppError;
+    use std::path::Path;
+
+    /// Driver reporting only the Ollama service as running.
+    struct OllamaOnlyDriver;
+
+    impl ProcessDriver for OllamaOnlyDriver {
+        fn spawn(&self, _service: &ManagedService, _log_path: &Path) -> Result<i32, AppError> {
+            Ok(1)
+        }
+
+        fn is_running(&self, service: &ManagedService, _pid: i32) -> bool {
+            service.name == "ollama"
+        }
+
+        fn is_running_by_signature(&self, service: &ManagedService) -> Option<i32> {
+            (service.name == "ollama").then_some(4321)
+        }
+
+        fn signal(
+            &self,
+            _service: &ManagedService,
+            _pid: i32,
+            _force: bool,
+        ) -> Result<bool, AppError> {
+            Ok(false)
+        }
+
+        fn health_port_open(&self, _service: &ManagedService) -> bool {
+            false
+        }
+
+        fn resource_usage(&self, _service: &ManagedService, _pid: i32) -> Option<ResourceUsage> {
+            Some(ResourceUsage { cpu_percent: 12.5, memory_bytes: 2048 })
+        }
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn render_prometheus_reports_up_and_down_services() {
+        let _project = TestProject::new();
+        let _guard = install_driver(Box::new(OllamaOnlyDriver));
+        let services = services::default_services(&Config::default()).unwrap();
+
+        let text = render_prometheus(&services);
+        assert!(text.contains("fusion_service_up{service=\"ollama\"} 1"));
+        assert!(text.contains("fusion_service_up{service=\"mlx\"} 0"));
+        assert!(text.contains("fusion_service_pid{service=\"ollama\"} 4321"));
+        assert!(!text.contains("fusion_service_pid{service=\"mlx\"}"));
+        assert!(text.contains("fusion_service_cpu_percent{service=\"ollama\"} 12.5"));
+        assert!(text.contains("fusion_service_memory_bytes{service=\"ollama\"} 2048"));
+        assert!(text.contains("# TYPE fusion_service_up gauge"));
+    }
+}
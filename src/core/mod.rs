@@ -1,7 +1,15 @@
+pub mod api;
 pub mod config;
+pub mod doctor;
 pub mod health;
+pub mod http;
+pub mod http_log;
+pub mod integration;
+pub mod log_stamp;
+pub mod metrics;
 pub mod paths;
 pub mod process;
+pub mod proxy;
 pub mod services;
 
 #[cfg(test)]
@@ -1,7 +1,17 @@
 use crate::error::AppError;
+use std::ffi::OsString;
 use std::path::PathBuf;
+use std::sync::RwLock;
 use std::{env, fs};
 
+/// Config file chosen with `fusion --config`, taking precedence over `FUSION_CONFIG_DIR`.
+static CONFIG_FILE_OVERRIDE: RwLock<Option<PathBuf>> = RwLock::new(None);
+
+/// Point every config load and save at `path` for the rest of the process (`None` clears it).
+pub fn set_config_file_override(path: Option<PathBuf>) {
+    *CONFIG_FILE_OVERRIDE.write().unwrap_or_else(|err| err.into_inner()) = path;
+}
+
 /// Resolve the project root directory for the CLI.
 pub fn project_root() -> PathBuf {
     env::var_os("FUSION_PROJECT_ROOT")
@@ -21,18 +31,37 @@ pub fn pid_dir() -> Result<PathBuf, AppError> {
 }
 
 /// Resolve the directory containing the persistent `config.toml` file.
+///
+/// `FUSION_CONFIG_DIR` wins, then `$XDG_CONFIG_HOME/fusion`, then `~/.config/fusion`.
 pub fn user_config_dir() -> Result<PathBuf, AppError> {
-    if let Some(override_dir) = env::var_os("FUSION_CONFIG_DIR") {
-        return Ok(PathBuf::from(override_dir));
-    }
+    resolve_config_dir(
+        env::var_os("FUSION_CONFIG_DIR"),
+        env::var_os("XDG_CONFIG_HOME"),
+        dirs::home_dir(),
+    )
+    .ok_or_else(|| AppError::config_error("Could not determine home directory"))
+}
 
-    dirs::home_dir()
-        .map(|dir| dir.join(".config").join("fusion"))
-        .ok_or_else(|| AppError::config_error("Could not determine home directory"))
+/// The XDG spec says relative `XDG_CONFIG_HOME` values are invalid and must be ignored.
+fn resolve_config_dir(
+    override_dir: Option<OsString>,
+    xdg_config_home: Option<OsString>,
+    home: Option<PathBuf>,
+) -> Option<PathBuf> {
+    if let Some(override_dir) = override_dir {
+        return Some(PathBuf::from(override_dir));
+    }
+    if let Some(xdg) = xdg_config_home.map(PathBuf::from).filter(|dir| dir.is_absolute()) {
+        return Some(xdg.join("fusion"));
+    }
+    home.map(|dir| dir.join(".config").join("fusion"))
 }
 
 /// Resolve the absolute path to the user's persistent configuration file.
 pub fn user_config_file() -> Result<PathBuf, AppError> {
+    if let Some(path) = CONFIG_FILE_OVERRIDE.read().unwrap_or_else(|err| err.into_inner()).clone() {
+        return Ok(path);
+    }
     Ok(user_config_dir()?.join("config.toml"))
 }
 
@@ -46,6 +75,20 @@ pub fn service_config_file(service_name: &str) -> Result<PathBuf, AppError> {
     Ok(service_state_dir(service_name)?.join("config.toml"))
 }
 
+/// Resolve the directory holding `systemd --user` unit files.
+pub fn systemd_user_dir() -> Result<PathBuf, AppError> {
+    dirs::config_dir()
+        .map(|dir| dir.join("systemd").join("user"))
+        .ok_or_else(|| AppError::config_error("Could not determine user config directory"))
+}
+
+/// Resolve the per-user launchd agents directory (`~/Library/LaunchAgents`).
+pub fn launch_agents_dir() -> Result<PathBuf, AppError> {
+    dirs::home_dir()
+        .map(|dir| dir.join("Library").join("LaunchAgents"))
+        .ok_or_else(|| AppError::config_error("Could not determine home directory"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -89,4 +132,28 @@ mod tests {
             env::remove_var("FUSION_CONFIG_DIR");
         }
     }
+
+    #[test]
+    fn config_dir_prefers_override_then_xdg_then_home() {
+        let home = Some(PathBuf::from("/home/user"));
+        let xdg = Some(OsString::from("/xdg/config"));
+
+        assert_eq!(
+            resolve_config_dir(None, xdg.clone(), home.clone()),
+            Some(PathBuf::from("/xdg/config/fusion"))
+        );
+        assert_eq!(
+            resolve_config_dir(Some("/custom".into()), xdg, home.clone()),
+            Some(PathBuf::from("/custom"))
+        );
+        assert_eq!(
+            resolve_config_dir(None, None, home.clone()),
+            Some(PathBuf::from("/home/user/.config/fusion"))
+        );
+        assert_eq!(
+            resolve_config_dir(None, Some("relative/config".into()), home),
+            Some(PathBuf::from("/home/user/.config/fusion"))
+        );
+        assert_eq!(resolve_config_dir(None, None, None), None);
+    }
 }
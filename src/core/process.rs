@@ -1,24 +1,76 @@
-use crate::core::paths;
 use crate::core::services::ManagedService;
+use crate::core::{config, log_stamp, paths};
 use crate::error::AppError;
-use std::fs::{self, OpenOptions};
-use std::io::{self, Write};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
 use std::mem;
+use std::net::{TcpStream, ToSocketAddrs};
 use std::path::Path;
 use std::process::{Command, Stdio};
-use std::sync::{LazyLock, Mutex, RwLock};
-use sysinfo::{Pid, Signal, System};
+use std::sync::{LazyLock, Mutex, PoisonError, RwLock};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+#[cfg(unix)]
+use sysinfo::Signal;
+use sysinfo::{Pid, Process, System};
+
+/// Connect timeout used when probing whether a service port is already bound.
+const PORT_PROBE_TIMEOUT_MS: u64 = 200;
+
+/// Block size for reading logs backwards in [`read_stderr_tail`].
+const TAIL_CHUNK_BYTES: u64 = 8 * 1024;
+
+/// Extra spawn attempts after a transient failure; `FUSION_START_RETRIES` overrides it.
+const DEFAULT_START_RETRIES: u32 = 2;
+
+/// Pause before retrying a transient spawn or port-in-use failure.
+const START_RETRY_DELAY: Duration = Duration::from_millis(300);
+
+/// How long a full process-table scan is reused before signature lookups rescan.
+const FULL_REFRESH_TTL: Duration = Duration::from_millis(500);
+
+/// Whether the platform can ask a process to exit gracefully (SIGTERM). Windows has no
+/// equivalent that sysinfo can deliver, so every stop there is a hard terminate.
+pub const GRACEFUL_STOP_SUPPORTED: bool = cfg!(unix);
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum StartOutcome {
-    Started { pid: i32 },
-    AlreadyRunning { pid: i32 },
+    /// `ready` is set when the driver already confirmed readiness while spawning.
+    Started {
+        pid: i32,
+        ready: bool,
+    },
+    AlreadyRunning {
+        pid: i32,
+    },
+    PortInUse {
+        port: u16,
+    },
+}
+
+/// A running process whose command line matches a service's command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignatureMatch {
+    pub pid: i32,
+    pub command: String,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum StopOutcome {
-    Stopped { pid: i32, forced: bool },
-    TerminatedByName { count: usize, forced: bool },
+    Stopped {
+        pid: i32,
+        forced: bool,
+    },
+    TerminatedByName {
+        count: usize,
+        forced: bool,
+    },
+    /// A graceful stop was requested but the platform could only terminate the process outright.
+    ForceTerminated {
+        pid: Option<i32>,
+        count: usize,
+    },
     NotRunning,
 }
 
@@ -28,26 +80,83 @@ pub enum StatusOutcome {
     NotRunning,
 }
 
+/// Point-in-time resource consumption of a running service process.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ResourceUsage {
+    pub cpu_percent: f32,
+    pub memory_bytes: u64,
+}
+
 pub trait ProcessDriver: Send + Sync {
     fn spawn(&self, service: &ManagedService, log_path: &Path) -> Result<i32, AppError>;
+    /// Spawn and, when the driver can observe readiness itself, block until `ready(pid)` passes.
+    /// Returns the PID and whether readiness was confirmed; the default only spawns and leaves
+    /// readiness to the HTTP probe.
+    fn spawn_with_ready_signal(
+        &self,
+        service: &ManagedService,
+        log_path: &Path,
+        _ready: &dyn Fn(i32) -> bool,
+    ) -> Result<(i32, bool), AppError> {
+        self.spawn(service, log_path).map(|pid| (pid, false))
+    }
     fn is_running(&self, service: &ManagedService, pid: i32) -> bool;
     fn is_running_by_signature(&self, service: &ManagedService) -> Option<i32>;
     fn signal(&self, service: &ManagedService, pid: i32, force: bool) -> Result<bool, AppError>;
-    fn kill_by_signature(&self, service: &ManagedService, force: bool) -> Result<usize, AppError>;
+    /// Every process matching the service's command signature; the default reports the first
+    /// match only.
+    fn processes_by_signature(&self, service: &ManagedService) -> Vec<SignatureMatch> {
+        self.is_running_by_signature(service)
+            .map(|pid| vec![SignatureMatch { pid, command: service.command_line() }])
+            .unwrap_or_default()
+    }
+    fn health_port_open(&self, service: &ManagedService) -> bool;
+    /// Report CPU and memory usage for `pid`; drivers without process introspection return `None`.
+    fn resource_usage(&self, _service: &ManagedService, _pid: i32) -> Option<ResourceUsage> {
+        None
+    }
+    /// Process start time in seconds since the Unix epoch, when the driver can tell.
+    fn started_at(&self, _service: &ManagedService, _pid: i32) -> Option<u64> {
+        None
+    }
+}
+
+/// Process snapshot shared across driver calls so batched queries reuse one full refresh.
+struct SystemState {
+    system: System,
+    last_full_refresh: Option<Instant>,
+    /// Full table scans so far, so tests can assert that lookups share one.
+    #[cfg(test)]
+    full_refreshes: usize,
 }
 
 struct SystemProcessDriver {
-    system: Mutex<System>,
+    state: Mutex<SystemState>,
 }
 
 impl SystemProcessDriver {
     fn new() -> Self {
-        Self { system: Mutex::new(System::new_all()) }
+        Self {
+            state: Mutex::new(SystemState {
+                system: System::new(),
+                last_full_refresh: None,
+                #[cfg(test)]
+                full_refreshes: 0,
+            }),
+        }
     }
 
-    fn with_system<R>(&self, f: impl FnOnce(&mut System) -> R) -> R {
-        let mut system = self.system.lock().expect("System lock poisoned");
-        f(&mut system)
+    fn with_state<R>(&self, f: impl FnOnce(&mut SystemState) -> R) -> R {
+        let mut state = self.state.lock().unwrap_or_else(|poisoned| {
+            tracing::warn!(
+                "process table lock was poisoned by a panic; discarding cached snapshot"
+            );
+            self.state.clear_poison();
+            let mut state = poisoned.into_inner();
+            Self::invalidate(&mut state);
+            state
+        });
+        f(&mut state)
     }
 
     fn expected_signature(service: &ManagedService) -> String {
@@ -58,23 +167,88 @@ impl SystemProcessDriver {
         if process.cmd().is_empty() { process.name().to_string() } else { process.cmd().join(" ") }
     }
 
-    fn matches_signature(expected: &str, process: &sysinfo::Process) -> bool {
-        Self::process_signature(process).contains(expected)
+    fn matches_signature(service: &ManagedService, process: &sysinfo::Process) -> bool {
+        // Cheap substring pre-filter, then confirm against the argument vector itself.
+        Self::process_signature(process).contains(&Self::expected_signature(service))
+            && command_matches(&service.command, process.cmd(), process.name())
     }
 
-    fn refresh_processes(system: &mut System) {
-        system.refresh_processes();
+    /// Refresh the full process table unless a refresh happened within `FULL_REFRESH_TTL`.
+    fn refresh_processes(state: &mut SystemState) {
+        let fresh = state.last_full_refresh.is_some_and(|at| at.elapsed() < FULL_REFRESH_TTL);
+        if !fresh {
+            state.system.refresh_processes();
+            state.last_full_refresh = Some(Instant::now());
+            #[cfg(test)]
+            {
+                state.full_refreshes += 1;
+            }
+        }
     }
+
+    /// Refresh a single process, returning it only if it still exists.
+    fn refresh_process(state: &mut SystemState, pid: i32) -> Option<&sysinfo::Process> {
+        let sys_pid = Pid::from_u32(pid as u32);
+        if state.system.refresh_process(sys_pid) { state.system.process(sys_pid) } else { None }
+    }
+
+    /// Force the next batch query to rescan, e.g. after signalling processes.
+    fn invalidate(state: &mut SystemState) {
+        state.last_full_refresh = None;
+    }
+}
+
+/// Whether a process command line runs `expected`: the executable basename must match and the
+/// remaining arguments must be identical. Console scripts such as `mlx_lm.server` may appear
+/// behind a Python interpreter, so `python3 /path/mlx_lm.server ...` also matches.
+fn command_matches(expected: &[String], cmd: &[String], name: &str) -> bool {
+    let Some((program, args)) = expected.split_first() else {
+        return false;
+    };
+    let program = executable_name(program);
+    if cmd.is_empty() {
+        return args.is_empty() && name == program;
+    }
+    let start = match cmd.first() {
+        Some(first) if executable_name(first) != program && is_interpreter(first) => 1,
+        _ => 0,
+    };
+    cmd.get(start).is_some_and(|arg| executable_name(arg) == program) && cmd[start + 1..] == *args
+}
+
+fn is_interpreter(path: &str) -> bool {
+    executable_name(path).to_ascii_lowercase().starts_with("python")
+}
+
+fn executable_name(path: &str) -> &str {
+    Path::new(path).file_name().and_then(|name| name.to_str()).unwrap_or(path)
 }
 
 static DRIVER: LazyLock<RwLock<Box<dyn ProcessDriver>>> =
     LazyLock::new(|| RwLock::new(Box::new(SystemProcessDriver::new())));
 
 fn with_driver<R>(f: impl FnOnce(&dyn ProcessDriver) -> R) -> R {
-    let guard = DRIVER.read().expect("process driver lock poisoned");
+    let guard = DRIVER.read().unwrap_or_else(recover_driver_lock);
     f(&**guard)
 }
 
+/// Query CPU and memory usage for a running service process, when the driver supports it.
+pub fn resource_usage(service: &ManagedService, pid: i32) -> Option<ResourceUsage> {
+    with_driver(|driver| driver.resource_usage(service, pid))
+}
+
+/// How long a running service process has been up, when the driver knows its start time.
+pub fn uptime(service: &ManagedService, pid: i32) -> Option<Duration> {
+    let started_at = with_driver(|driver| driver.started_at(service, pid))?;
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?.as_secs();
+    Some(Duration::from_secs(now.saturating_sub(started_at)))
+}
+
+/// Whether something is accepting connections on the service's host and port.
+pub fn is_port_open(service: &ManagedService) -> bool {
+    with_driver(|driver| driver.health_port_open(service))
+}
+
 /// Lightweight helper to check if a process is alive.
 pub fn is_process_alive(service: &ManagedService, pid: i32) -> bool {
     with_driver(|driver| driver.is_running(service, pid))
@@ -82,8 +256,7 @@ pub fn is_process_alive(service: &ManagedService, pid: i32) -> bool {
 
 impl ProcessDriver for SystemProcessDriver {
     fn spawn(&self, service: &ManagedService, log_path: &Path) -> Result<i32, AppError> {
-        let stdout = OpenOptions::new().create(true).append(true).open(log_path)?;
-        let stderr = OpenOptions::new().create(true).append(true).open(log_path)?;
+        let (stdout, stderr) = log_streams(service, log_path)?;
 
         let mut command =
             Command::new(service.command.first().ok_or_else(|| {
@@ -98,105 +271,325 @@ impl ProcessDriver for SystemProcessDriver {
         }
 
         command.stdin(Stdio::null());
-        command.stdout(Stdio::from(stdout));
-        command.stderr(Stdio::from(stderr));
+        command.stdout(stdout);
+        command.stderr(stderr);
+        #[cfg(unix)]
+        detach_from_terminal(&mut command);
         let child = command.spawn().map_err(|err| {
-            AppError::process_error(service.name, format!("failed to spawn: {err}"))
+            let message = format!("failed to spawn: {err}");
+            if is_transient_kind(err.kind()) {
+                // Kept as an I/O error so `start_service` can tell it is worth retrying; it becomes
+                // a process error again if the retries run out.
+                AppError::Io(io::Error::new(err.kind(), message))
+            } else {
+                AppError::process_error(service.name, message)
+            }
         })?;
         Ok(child.id() as i32)
     }
 
     fn is_running(&self, service: &ManagedService, pid: i32) -> bool {
-        let expected = Self::expected_signature(service);
-        self.with_system(|system| {
-            Self::refresh_processes(system);
-            let sys_pid = Pid::from_u32(pid as u32);
-            system
-                .process(sys_pid)
-                .map(|process| Self::matches_signature(&expected, process))
+        self.with_state(|state| {
+            Self::refresh_process(state, pid)
+                .map(|process| Self::matches_signature(service, process))
                 .unwrap_or(false)
         })
     }
 
     fn is_running_by_signature(&self, service: &ManagedService) -> Option<i32> {
-        let expected = Self::expected_signature(service);
-        self.with_system(|system| {
-            Self::refresh_processes(system);
-            system
+        self.with_state(|state| {
+            Self::refresh_processes(state);
+            state
+                .system
                 .processes()
                 .values()
-                .find(|process| Self::matches_signature(&expected, process))
+                .find(|process| Self::matches_signature(service, process))
                 .map(|process| process.pid().as_u32() as i32)
         })
     }
 
     fn signal(&self, service: &ManagedService, pid: i32, force: bool) -> Result<bool, AppError> {
-        let expected = Self::expected_signature(service);
-        self.with_system(|system| {
-            Self::refresh_processes(system);
-            let sys_pid = Pid::from_u32(pid as u32);
-            if let Some(process) = system.process(sys_pid) {
-                if !Self::matches_signature(&expected, process) {
-                    return Ok(false);
+        self.with_state(|state| {
+            let signaled = match Self::refresh_process(state, pid) {
+                Some(process) if Self::matches_signature(service, process) => {
+                    terminate(process, force)
                 }
-                let signal = if force { Signal::Kill } else { Signal::Term };
-                Ok(process.kill_with(signal).unwrap_or(false))
-            } else {
-                Ok(false)
-            }
+                _ => false,
+            };
+            Self::invalidate(state);
+            Ok(signaled)
         })
     }
 
-    fn kill_by_signature(&self, service: &ManagedService, force: bool) -> Result<usize, AppError> {
-        let expected = Self::expected_signature(service);
-        self.with_system(|system| {
-            Self::refresh_processes(system);
-            let signal = if force { Signal::Kill } else { Signal::Term };
-            let mut killed = 0;
-            for process in system.processes().values() {
-                if Self::matches_signature(&expected, process)
-                    && process.kill_with(signal).unwrap_or(false)
-                {
-                    killed += 1;
-                }
+    fn processes_by_signature(&self, service: &ManagedService) -> Vec<SignatureMatch> {
+        self.with_state(|state| {
+            // The matches may be killed, so never trust a cached snapshot here.
+            Self::invalidate(state);
+            Self::refresh_processes(state);
+            let mut matches: Vec<SignatureMatch> = state
+                .system
+                .processes()
+                .values()
+                .filter(|process| Self::matches_signature(service, process))
+                .map(|process| SignatureMatch {
+                    pid: process.pid().as_u32() as i32,
+                    command: Self::process_signature(process),
+                })
+                .collect();
+            matches.sort_by_key(|found| found.pid);
+            matches
+        })
+    }
+
+    fn health_port_open(&self, service: &ManagedService) -> bool {
+        let address = config::format_host_port(&service.host, service.port);
+        let timeout = Duration::from_millis(PORT_PROBE_TIMEOUT_MS);
+        match address.to_socket_addrs() {
+            Ok(addrs) => {
+                addrs.into_iter().any(|addr| TcpStream::connect_timeout(&addr, timeout).is_ok())
             }
-            Ok(killed)
+            Err(_) => false,
+        }
+    }
+
+    fn resource_usage(&self, service: &ManagedService, pid: i32) -> Option<ResourceUsage> {
+        let matches = self.with_state(|state| {
+            Self::refresh_process(state, pid)
+                .is_some_and(|process| Self::matches_signature(service, process))
+        });
+        if matches { measure_usage(pid) } else { None }
+    }
+
+    fn started_at(&self, service: &ManagedService, pid: i32) -> Option<u64> {
+        self.with_state(|state| {
+            Self::refresh_process(state, pid)
+                .filter(|process| Self::matches_signature(service, process))
+                .map(Process::start_time)
         })
     }
 }
 
+/// CPU and memory of `pid`. CPU usage is the delta between two refreshes at least
+/// `MINIMUM_CPU_UPDATE_INTERVAL` apart; a private `System` keeps that wait from holding up other
+/// queries on the shared snapshot.
+fn measure_usage(pid: i32) -> Option<ResourceUsage> {
+    let sys_pid = Pid::from_u32(pid as u32);
+    let mut system = System::new();
+    system.refresh_process(sys_pid);
+    std::thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+    system.refresh_process(sys_pid);
+    system.process(sys_pid).map(|process| ResourceUsage {
+        cpu_percent: process.cpu_usage(),
+        memory_bytes: process.memory(),
+    })
+}
+
+/// Start the child in a new session with SIGHUP ignored, so neither closing the terminal nor
+/// Ctrl-C in the shell that ran `fusion up` reaches it. Applied to the server and to the log
+/// relays its stdout and stderr are piped through.
+#[cfg(unix)]
+fn detach_from_terminal(command: &mut Command) {
+    use std::os::unix::process::CommandExt;
+    // SAFETY: `setsid` and `signal` are async-signal-safe and touch no state of the parent.
+    unsafe {
+        command.pre_exec(|| {
+            if libc::setsid() == -1 {
+                return Err(io::Error::last_os_error());
+            }
+            libc::signal(libc::SIGHUP, libc::SIG_IGN);
+            Ok(())
+        });
+    }
+}
+
+/// Send SIGTERM, or SIGKILL when `force` is set.
+#[cfg(unix)]
+fn terminate(process: &Process, force: bool) -> bool {
+    let signal = if force { Signal::Kill } else { Signal::Term };
+    process.kill_with(signal).unwrap_or(false)
+}
+
+/// Windows cannot deliver SIGTERM, so both graceful and forced stops terminate the process.
+#[cfg(not(unix))]
+fn terminate(process: &Process, _force: bool) -> bool {
+    process.kill()
+}
+
+/// Classify a successful stop, flagging graceful requests the platform had to force.
+fn stopped_outcome(pid: Option<i32>, count: usize, force: bool, graceful: bool) -> StopOutcome {
+    match pid {
+        _ if !force && !graceful => StopOutcome::ForceTerminated { pid, count },
+        Some(pid) => StopOutcome::Stopped { pid, forced: force },
+        None => StopOutcome::TerminatedByName { count, forced: force },
+    }
+}
+
+/// Whether a command only reads a service's state files or may rewrite them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LockMode {
+    Shared,
+    Exclusive,
+}
+
+/// Take the service's advisory lock, blocking until it is free. Released when the file drops.
+fn lock_service(service: &ManagedService, mode: LockMode) -> Result<File, AppError> {
+    let path = service.lock_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let file = OpenOptions::new().create(true).truncate(false).write(true).open(&path)?;
+    match mode {
+        LockMode::Shared => file.lock_shared()?,
+        LockMode::Exclusive => file.lock()?,
+    }
+    Ok(file)
+}
+
 pub fn start_service(service: &ManagedService) -> Result<StartOutcome, AppError> {
+    start_service_with_ready_signal(service, None)
+}
+
+/// Like [`start_service`], but lets drivers that support it confirm readiness through `ready`.
+#[tracing::instrument(skip_all, fields(service = service.name, port = service.port))]
+pub fn start_service_with_ready_signal(
+    service: &ManagedService,
+    ready: Option<&dyn Fn(i32) -> bool>,
+) -> Result<StartOutcome, AppError> {
     ensure_pid_dir()?;
+    // Held until the PID is recorded, so a concurrent `up` sees it instead of spawning again.
+    let _lock = lock_service(service, LockMode::Exclusive)?;
 
     if let Some(pid) = read_pid(service)? {
         if with_driver(|driver| driver.is_running(service, pid)) {
+            tracing::info!(pid, "service already running");
             return Ok(StartOutcome::AlreadyRunning { pid });
         }
+        tracing::debug!(pid, "removing stale pid file");
         remove_pid(service)?;
     }
 
+    // Another process (e.g. a manually launched runtime) may already own the port. A server that
+    // was just stopped can hold it for a moment, so only give up once it stays bound.
+    let retries = start_retries(std::env::var("FUSION_START_RETRIES").ok().as_deref());
+    let mut attempt = 0;
+    while with_driver(|driver| driver.health_port_open(service)) {
+        if attempt == retries {
+            tracing::info!("port already in use");
+            return Ok(StartOutcome::PortInUse { port: service.port });
+        }
+        attempt += 1;
+        tracing::debug!(attempt, "port in use; rechecking");
+        std::thread::sleep(START_RETRY_DELAY);
+    }
+
     let log_path = service.log_path()?;
     if let Some(parent) = log_path.parent() {
         fs::create_dir_all(parent)?;
     }
 
     reset_log_file(&log_path)?;
+    reset_log_file(&service.stderr_log_path()?)?;
 
-    let pid = with_driver(|driver| driver.spawn(service, &log_path))?;
-    write_pid(service, pid)?;
+    tracing::debug!(command = %service.command_line(), log = %log_path.display(), "spawning");
+    let (pid, ready) = retry_transient(service, retries, || {
+        with_driver(|driver| match ready {
+            Some(ready) => driver.spawn_with_ready_signal(service, &log_path, ready),
+            None => driver.spawn(service, &log_path).map(|pid| (pid, false)),
+        })
+    })?;
+    let recorded = write_pid_unless_live(service, pid)?;
+    if recorded != pid {
+        // Another command started the service while this one was spawning; keep theirs.
+        tracing::warn!(pid, recorded, "service started concurrently; stopping duplicate");
+        with_driver(|driver| driver.signal(service, pid, false))?;
+        return Ok(StartOutcome::AlreadyRunning { pid: recorded });
+    }
     write_config(service)?;
+    tracing::info!(pid, ready, "spawned and recorded pid");
 
-    Ok(StartOutcome::Started { pid })
+    Ok(StartOutcome::Started { pid, ready })
 }
 
+/// Spawn failures worth another attempt: the binary or another resource was briefly busy, or the
+/// call was interrupted. A port still in use is not a spawn error; `start_service` rechecks it.
+fn is_transient_kind(kind: io::ErrorKind) -> bool {
+    matches!(
+        kind,
+        io::ErrorKind::ExecutableFileBusy
+            | io::ErrorKind::Interrupted
+            | io::ErrorKind::WouldBlock
+            | io::ErrorKind::ResourceBusy
+            | io::ErrorKind::TimedOut
+    )
+}
+
+/// Extra spawn attempts: `FUSION_START_RETRIES` when it parses, otherwise the default.
+fn start_retries(raw: Option<&str>) -> u32 {
+    raw.and_then(|value| value.trim().parse().ok()).unwrap_or(DEFAULT_START_RETRIES)
+}
+
+/// Run `attempt`, retrying up to `retries` more times while it fails with a transient I/O error.
+/// A transient failure that outlasts the retries is reported as a process error, like any other
+/// failed spawn.
+fn retry_transient<T>(
+    service: &ManagedService,
+    retries: u32,
+    mut attempt: impl FnMut() -> Result<T, AppError>,
+) -> Result<T, AppError> {
+    let mut failures = 0;
+    loop {
+        match attempt() {
+            Err(AppError::Io(err)) if is_transient_kind(err.kind()) => {
+                if failures == retries {
+                    return Err(AppError::process_error(service.name, err.to_string()));
+                }
+                failures += 1;
+                tracing::warn!(attempt = failures, error = %err, "transient spawn failure; retrying");
+                std::thread::sleep(START_RETRY_DELAY);
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Fail with the tail of the stderr log when `pid` exited during startup.
+pub fn ensure_alive(service: &ManagedService, pid: i32) -> Result<(), AppError> {
+    if is_process_alive(service, pid) {
+        return Ok(());
+    }
+    let log_tail = read_stderr_tail(service, 10).unwrap_or_default();
+    let log_path =
+        service.stderr_log_path().map(|path| path.display().to_string()).unwrap_or_default();
+    let detected_at = log_stamp::now_timestamp();
+    Err(AppError::process_error(
+        service.name,
+        format!(
+            "Process {pid} died unexpectedly during startup (noticed at {detected_at}).\nLast lines of {log_path}:\n{log_tail}"
+        ),
+    ))
+}
+
+#[tracing::instrument(skip_all, fields(service = service.name, force = force))]
 pub fn stop_service(service: &ManagedService, force: bool) -> Result<StopOutcome, AppError> {
+    stop_service_confirmed(service, force, &mut |_| true)
+}
+
+/// Like [`stop_service`], but a forced stop that falls back to killing by signature first shows
+/// the matched processes to `confirm`, and kills nothing unless it returns `true`.
+pub fn stop_service_confirmed(
+    service: &ManagedService,
+    force: bool,
+    confirm: &mut dyn FnMut(&[SignatureMatch]) -> bool,
+) -> Result<StopOutcome, AppError> {
+    let lock = lock_service(service, LockMode::Exclusive)?;
     if let Some(pid) = read_pid(service)? {
         if with_driver(|driver| driver.is_running(service, pid)) {
             let signaled = with_driver(|driver| driver.signal(service, pid, force))?;
+            tracing::info!(pid, signaled, "signaled pid from pid file");
             if signaled {
                 remove_pid(service)?;
                 remove_config(service)?;
-                return Ok(StopOutcome::Stopped { pid, forced: force });
+                return Ok(stopped_outcome(Some(pid), 1, force, GRACEFUL_STOP_SUPPORTED));
             }
             remove_pid(service)?;
             remove_config(service)?;
@@ -206,15 +599,72 @@ pub fn stop_service(service: &ManagedService, force: bool) -> Result<StopOutcome
         }
     }
 
-    let killed = with_driver(|driver| driver.kill_by_signature(service, force))?;
+    let matches = if force {
+        with_driver(|driver| driver.processes_by_signature(service))
+    } else {
+        Vec::new()
+    };
+    // The prompt may wait on the user, so other commands are not held up meanwhile.
+    let _lock = if matches.is_empty() {
+        lock
+    } else {
+        drop(lock);
+        if !confirm(&matches) {
+            return Err(AppError::process_error(
+                service.name,
+                "force stop cancelled; no processes were killed",
+            ));
+        }
+        lock_service(service, LockMode::Exclusive)?
+    };
+    // Only the processes that were shown are signalled. `signal` re-checks each signature, so a
+    // PID recycled by an unrelated process while the prompt was open is skipped.
+    let mut killed = 0;
+    for found in &matches {
+        if with_driver(|driver| driver.signal(service, found.pid, force))? {
+            killed += 1;
+        }
+    }
+    tracing::info!(killed, "signaled processes matching the command signature");
     if killed > 0 {
-        return Ok(StopOutcome::TerminatedByName { count: killed, forced: force });
+        return Ok(stopped_outcome(None, killed, force, GRACEFUL_STOP_SUPPORTED));
     }
 
     Ok(StopOutcome::NotRunning)
 }
 
+/// Status of each service in order, querying up to `count` of them at once on scoped threads.
+pub fn status_services(
+    services: &[ManagedService],
+    count: usize,
+) -> Result<Vec<StatusOutcome>, AppError> {
+    let mut outcomes = Vec::with_capacity(services.len());
+    for batch in services.chunks(count.max(1)) {
+        let results: Vec<_> = std::thread::scope(|scope| {
+            let queries: Vec<_> =
+                batch.iter().map(|service| scope.spawn(|| status_service(service))).collect();
+            queries.into_iter().map(|query| query.join().expect("status query panicked")).collect()
+        });
+        for result in results {
+            outcomes.push(result?);
+        }
+    }
+    Ok(outcomes)
+}
+
 pub fn status_service(service: &ManagedService) -> Result<StatusOutcome, AppError> {
+    {
+        let _lock = lock_service(service, LockMode::Shared)?;
+        if let Some(pid) = read_pid(service)?
+            && with_driver(|driver| driver.is_running(service, pid))
+        {
+            return Ok(StatusOutcome::Running { pid });
+        }
+    }
+
+    // Reconciling rewrites the PID file, so it needs the exclusive lock; the PID is read again
+    // because another command may have changed it between the two locks.
+    let _lock = lock_service(service, LockMode::Exclusive)?;
     if let Some(pid) = read_pid(service)? {
         if with_driver(|driver| driver.is_running(service, pid)) {
             return Ok(StatusOutcome::Running { pid });
@@ -224,8 +674,8 @@ pub fn status_service(service: &ManagedService) -> Result<StatusOutcome, AppErro
 
     // Check if any process matches the signature (for daemonized services)
     if let Some(pid) = with_driver(|driver| driver.is_running_by_signature(service)) {
-        // Write the PID file for future checks
-        write_pid(service, pid)?;
+        // Record it for future checks, unless an `up` has meanwhile written its own live PID.
+        let pid = write_pid_unless_live(service, pid)?;
         return Ok(StatusOutcome::Running { pid });
     }
 
@@ -264,6 +714,19 @@ pub fn write_pid(service: &ManagedService, pid: i32) -> Result<(), AppError> {
     Ok(())
 }
 
+/// Record `pid` unless the PID file already names a different live process, which a concurrent
+/// command may have written since this one last looked. Returns the PID left in the file.
+pub fn write_pid_unless_live(service: &ManagedService, pid: i32) -> Result<i32, AppError> {
+    if let Some(recorded) = read_pid(service)?
+        && recorded != pid
+        && with_driver(|driver| driver.is_running(service, recorded))
+    {
+        return Ok(recorded);
+    }
+    write_pid(service, pid)?;
+    Ok(pid)
+}
+
 pub fn remove_pid(service: &ManagedService) -> Result<(), AppError> {
     let path = service.pid_path()?;
     match fs::remove_file(path) {
@@ -273,48 +736,83 @@ pub fn remove_pid(service: &ManagedService) -> Result<(), AppError> {
     }
 }
 
+/// Snapshot of how a service was launched, persisted next to its PID file.
+///
+/// `command` and `env` are empty when read from sidecars written by older releases, which only
+/// recorded `host=` and `port=` lines.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuntimeConfig {
+    pub host: String,
+    pub port: u16,
+    #[serde(default)]
+    pub command: Vec<String>,
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+}
+
 pub fn write_config(service: &ManagedService) -> Result<(), AppError> {
     ensure_pid_dir()?;
     let path = service.config_path()?;
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent)?;
     }
+    let runtime = RuntimeConfig {
+        host: service.host.clone(),
+        port: service.port,
+        command: service.command.clone(),
+        env: service.env.iter().map(|(key, value)| (key.clone(), value.clone())).collect(),
+    };
+    let contents = toml::to_string(&runtime).map_err(|err| {
+        AppError::process_error(service.name, format!("failed to serialise runtime config: {err}"))
+    })?;
     let mut handle = OpenOptions::new().create(true).write(true).truncate(true).open(path)?;
-    writeln!(handle, "host={}", service.host)?;
-    writeln!(handle, "port={}", service.port)?;
+    handle.write_all(contents.as_bytes())?;
     Ok(())
 }
 
-pub fn read_config(service: &ManagedService) -> Result<Option<(String, u16)>, AppError> {
+pub fn read_config(service: &ManagedService) -> Result<Option<RuntimeConfig>, AppError> {
     let path = service.config_path()?;
     match fs::read_to_string(&path) {
-        Ok(contents) => {
-            let mut host = None;
-            let mut port = None;
-            for line in contents.lines() {
-                let line = line.trim();
-                if line.is_empty() {
-                    continue;
-                }
-                if let Some((key, value)) = line.split_once('=') {
-                    match key.trim() {
-                        "host" => host = Some(value.trim().to_string()),
-                        "port" => {
-                            port = Some(value.trim().parse::<u16>().map_err(|_| {
-                                AppError::process_error(
-                                    service.name,
-                                    format!("invalid port value '{}'", value.trim()),
-                                )
-                            })?)
-                        }
-                        _ => {}
-                    }
+        Ok(contents) => match toml::from_str::<RuntimeConfig>(&contents) {
+            Ok(runtime) => Ok(Some(runtime)),
+            Err(_) => read_legacy_config(service, &contents),
+        },
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Parse the original `host=...`/`port=...` sidecar format.
+fn read_legacy_config(
+    service: &ManagedService,
+    contents: &str,
+) -> Result<Option<RuntimeConfig>, AppError> {
+    let mut host = None;
+    let mut port = None;
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if let Some((key, value)) = line.split_once('=') {
+            match key.trim() {
+                "host" => host = Some(value.trim().to_string()),
+                "port" => {
+                    port = Some(value.trim().parse::<u16>().map_err(|_| {
+                        AppError::process_error(
+                            service.name,
+                            format!("invalid port value '{}'", value.trim()),
+                        )
+                    })?)
                 }
+                _ => {}
             }
-            if let (Some(h), Some(p)) = (host, port) { Ok(Some((h, p))) } else { Ok(None) }
         }
-        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
-        Err(err) => Err(err.into()),
+    }
+    if let (Some(host), Some(port)) = (host, port) {
+        Ok(Some(RuntimeConfig { host, port, command: Vec::new(), env: BTreeMap::new() }))
+    } else {
+        Ok(None)
     }
 }
 
@@ -331,6 +829,27 @@ fn ensure_pid_dir() -> Result<(), AppError> {
     paths::ensure_pid_dir().map(|_| ())
 }
 
+/// Server stdout goes to `log_path` and stderr to the service's separate stderr log.
+fn log_streams(service: &ManagedService, log_path: &Path) -> Result<(Stdio, Stdio), AppError> {
+    Ok((log_stream(service, log_path)?, log_stream(service, &service.stderr_log_path()?)?))
+}
+
+/// Route one output stream through the timestamping relay when available, else straight to `path`.
+fn log_stream(service: &ManagedService, path: &Path) -> Result<Stdio, AppError> {
+    let Some(mut stamper) = log_stamp::stamper_command(path) else {
+        return Ok(Stdio::from(OpenOptions::new().create(true).append(true).open(path)?));
+    };
+    let (reader, writer) = io::pipe()?;
+    stamper.stdin(reader).stdout(Stdio::null()).stderr(Stdio::null());
+    // The server dies of SIGPIPE without its relay, so the relay must outlive the terminal too.
+    #[cfg(unix)]
+    detach_from_terminal(&mut stamper);
+    stamper.spawn().map_err(|err| {
+        AppError::process_error(service.name, format!("failed to start log relay: {err}"))
+    })?;
+    Ok(Stdio::from(writer))
+}
+
 fn reset_log_file(path: &Path) -> Result<(), AppError> {
     OpenOptions::new().create(true).write(true).truncate(true).open(path)?;
     Ok(())
@@ -338,22 +857,49 @@ fn reset_log_file(path: &Path) -> Result<(), AppError> {
 
 /// Read the last `lines` lines of the service's stderr log.
 pub fn read_stderr_tail(service: &ManagedService, lines: usize) -> Option<String> {
-    let log_path = service.log_path().ok()?;
-    let contents = fs::read_to_string(log_path).ok()?;
+    let log_path = service.stderr_log_path().ok()?;
+    tail_file(&log_path, lines).ok().map(|tail| tail.join("\n"))
+}
 
+/// Last `lines` lines of `path`, read backwards in chunks so large logs are not loaded whole.
+pub fn tail_file(path: &Path, lines: usize) -> io::Result<Vec<String>> {
+    let mut file = File::open(path)?;
+    let len = file.metadata()?.len();
+    tail_reader(&mut file, len, lines)
+}
+
+fn tail_reader(reader: &mut (impl Read + Seek), len: u64, lines: usize) -> io::Result<Vec<String>> {
     if lines == 0 {
-        return Some(String::new());
+        return Ok(Vec::new());
     }
-
+    let mut end = len;
     let mut buffer = Vec::new();
-    for line in contents.lines() {
-        if buffer.len() == lines {
-            buffer.remove(0);
-        }
-        buffer.push(line.to_string());
+    let mut newlines = 0;
+    // One extra newline marks the start of the oldest wanted line; a trailing one ends the file.
+    while end > 0 && newlines <= lines {
+        let start = end.saturating_sub(TAIL_CHUNK_BYTES);
+        let mut chunk = vec![0; (end - start) as usize];
+        reader.seek(SeekFrom::Start(start))?;
+        reader.read_exact(&mut chunk)?;
+        newlines += chunk.iter().filter(|byte| **byte == b'\n').count();
+        chunk.extend_from_slice(&buffer);
+        buffer = chunk;
+        end = start;
     }
+    let text = String::from_utf8_lossy(&buffer);
+    let all: Vec<&str> = text.lines().collect();
+    let skip = all.len().saturating_sub(lines);
+    Ok(all[skip..].iter().map(|line| line.to_string()).collect())
+}
 
-    Some(buffer.join("\n"))
+/// A panic while swapping drivers leaves the installed driver intact, so keep using it rather
+/// than taking down every later command in long-running modes like `proxy` or `ps --watch`.
+fn recover_driver_lock<G>(poisoned: PoisonError<G>) -> G {
+    tracing::warn!(
+        "process driver lock was poisoned by a panic; continuing with the current driver"
+    );
+    DRIVER.clear_poison();
+    poisoned.into_inner()
 }
 
 pub struct DriverGuard {
@@ -361,7 +907,7 @@ pub struct DriverGuard {
 }
 
 pub fn install_driver(driver: Box<dyn ProcessDriver>) -> DriverGuard {
-    let mut guard = DRIVER.write().expect("process driver lock poisoned");
+    let mut guard = DRIVER.write().unwrap_or_else(recover_driver_lock);
     let previous = mem::replace(&mut *guard, driver);
     DriverGuard { previous: Some(previous) }
 }
@@ -369,7 +915,7 @@ pub fn install_driver(driver: Box<dyn ProcessDriver>) -> DriverGuard {
 impl Drop for DriverGuard {
     fn drop(&mut self) {
         if let Some(previous) = self.previous.take() {
-            let mut guard = DRIVER.write().expect("process driver lock poisoned");
+            let mut guard = DRIVER.write().unwrap_or_else(recover_driver_lock);
             *guard = previous;
         }
     }
@@ -378,19 +924,24 @@ impl Drop for DriverGuard {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::services::ServiceType;
     use crate::core::test_support::TestProject;
     use std::collections::HashMap;
 
     fn service(_project: &TestProject) -> ManagedService {
         ManagedService {
             name: "test",
+            service_type: ServiceType::Mlx,
             host: "127.0.0.1".into(),
             port: 4242,
+            scheme: "http".into(),
             command: vec!["dummy".into()],
-            log_filename: "test.log",
-            pid_filename: "test.pid",
-            config_filename: "test.config",
+            log_filename: "test.log".into(),
+            pid_filename: "test.pid".into(),
+            config_filename: "test.config".into(),
             env: HashMap::new(),
+            headers: Default::default(),
+            ready_check: None,
         }
     }
 
@@ -429,4 +980,556 @@ mod tests {
         assert!(matches!(status, StatusOutcome::NotRunning));
         assert!(!svc.pid_path().unwrap().exists(), "stale pid file should be removed");
     }
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn command_matches_requires_exact_program_and_arguments() {
+        let ollama = args(&["ollama", "serve"]);
+        assert!(command_matches(&ollama, &args(&["/usr/local/bin/ollama", "serve"]), "ollama"));
+        assert!(!command_matches(&ollama, &args(&["ollama-helper", "serve"]), "ollama-helper"));
+        assert!(!command_matches(&ollama, &args(&["vim", "ollama", "serve"]), "vim"));
+        assert!(!command_matches(&ollama, &args(&["vim", "ollama.config"]), "vim"));
+        assert!(!command_matches(&ollama, &args(&["ollama", "serve", "--extra"]), "ollama"));
+        assert!(!command_matches(&ollama, &args(&["myollama", "serve"]), "myollama"));
+
+        let mlx = args(&["mlx_lm.server", "--model", "m", "--port", "8080"]);
+        let python =
+            args(&["python3", "/venv/bin/mlx_lm.server", "--model", "m", "--port", "8080"]);
+        assert!(command_matches(&mlx, &python, "python3"));
+        let other_port = args(&["mlx_lm.server", "--model", "m", "--port", "80800"]);
+        assert!(!command_matches(&mlx, &other_port, "mlx_lm.server"));
+
+        assert!(command_matches(&args(&["dummy"]), &[], "dummy"));
+        assert!(!command_matches(&args(&["dummy"]), &[], "dummy-daemon"));
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn signature_lookups_share_one_full_refresh() {
+        let project = TestProject::new();
+        let driver = SystemProcessDriver::new();
+        let ollama = ManagedService {
+            name: "ollama",
+            service_type: ServiceType::Ollama,
+            ..service(&project)
+        };
+        let mlx =
+            ManagedService { name: "mlx", command: vec!["other".into()], ..service(&project) };
+
+        assert!(driver.is_running_by_signature(&ollama).is_none());
+        assert!(driver.is_running_by_signature(&mlx).is_none());
+        assert!(!driver.is_running(&ollama, i32::MAX));
+        assert_eq!(driver.with_state(|state| state.full_refreshes), 1);
+
+        driver.processes_by_signature(&mlx);
+        assert_eq!(driver.with_state(|state| state.full_refreshes), 2);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn start_service_reports_port_in_use() {
+        let project = TestProject::new();
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("listener should bind");
+        let mut svc = service(&project);
+        svc.port = listener.local_addr().unwrap().port();
+
+        let outcome = start_service(&svc).expect("start should succeed");
+        assert_eq!(outcome, StartOutcome::PortInUse { port: svc.port });
+        assert!(!svc.pid_path().unwrap().exists(), "no pid file should be written");
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn stderr_tail_reads_last_lines_across_chunks() {
+        let project = TestProject::new();
+        let svc = service(&project);
+        paths::ensure_pid_dir().unwrap();
+        fs::create_dir_all(svc.log_path().unwrap().parent().unwrap()).unwrap();
+        fs::write(svc.log_path().unwrap(), "stdout only\n").unwrap();
+        let log: String = (0..5_000).map(|n| format!("2024-02-29T12:00:00Z line {n}\n")).collect();
+        assert!(log.len() as u64 > 4 * TAIL_CHUNK_BYTES);
+        fs::write(svc.stderr_log_path().unwrap(), &log).unwrap();
+
+        let tail = read_stderr_tail(&svc, 3).expect("log should be readable");
+        assert_eq!(
+            tail,
+            "2024-02-29T12:00:00Z line 4997\n\
+             2024-02-29T12:00:00Z line 4998\n\
+             2024-02-29T12:00:00Z line 4999"
+        );
+        assert_eq!(read_stderr_tail(&svc, 0).as_deref(), Some(""));
+
+        fs::write(svc.stderr_log_path().unwrap(), "only line without newline").unwrap();
+        assert_eq!(read_stderr_tail(&svc, 10).as_deref(), Some("only line without newline"));
+    }
+
+    #[test]
+    fn tail_file_reads_only_the_end_of_a_large_file() {
+        /// Counts the bytes pulled from the wrapped reader.
+        struct Counting<R> {
+            inner: R,
+            read: u64,
+        }
+        impl<R: Read> Read for Counting<R> {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                let n = self.inner.read(buf)?;
+                self.read += n as u64;
+                Ok(n)
+            }
+        }
+        impl<R: Seek> Seek for Counting<R> {
+            fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+                self.inner.seek(pos)
+            }
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("big.log");
+        let log: String = (0..200_000).map(|n| format!("line {n}\n")).collect();
+        fs::write(&path, &log).unwrap();
+
+        assert_eq!(tail_file(&path, 2).unwrap(), ["line 199998", "line 199999"]);
+        let mut reader = Counting { inner: File::open(&path).unwrap(), read: 0 };
+        let tail = tail_reader(&mut reader, log.len() as u64, 500).unwrap();
+        assert_eq!(tail.len(), 500);
+        assert_eq!(tail[0], "line 199500");
+        assert!(reader.read <= 2 * TAIL_CHUNK_BYTES, "read {} bytes", reader.read);
+        assert!(tail_file(&path, 0).unwrap().is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    #[serial_test::serial]
+    fn spawn_writes_stdout_and_stderr_to_separate_logs() {
+        let project = TestProject::new();
+        let mut svc = service(&project);
+        svc.command = args(&["sh", "-c", "echo to-stdout; echo to-stderr >&2"]);
+        let log_path = svc.log_path().unwrap();
+        fs::create_dir_all(log_path.parent().unwrap()).unwrap();
+
+        SystemProcessDriver::new().spawn(&svc, &log_path).expect("spawn should succeed");
+        let stderr_path = svc.stderr_log_path().unwrap();
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while fs::read_to_string(&stderr_path).unwrap_or_default().is_empty()
+            && Instant::now() < deadline
+        {
+            std::thread::sleep(Duration::from_millis(20));
+        }
+
+        assert_ne!(log_path, stderr_path);
+        assert_eq!(fs::read_to_string(&log_path).unwrap(), "to-stdout\n");
+        assert_eq!(read_stderr_tail(&svc, 5).as_deref(), Some("to-stderr"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    #[serial_test::serial]
+    fn spawned_server_leads_its_own_session_and_survives_sighup() {
+        let project = TestProject::new();
+        let mut svc = service(&project);
+        svc.command = args(&["sleep", "30"]);
+        let log_path = svc.log_path().unwrap();
+        fs::create_dir_all(log_path.parent().unwrap()).unwrap();
+
+        let pid = SystemProcessDriver::new().spawn(&svc, &log_path).expect("spawn should succeed");
+        // SAFETY: plain syscalls on a child this test owns.
+        unsafe {
+            assert_eq!(libc::getpgid(pid), pid);
+            assert_eq!(libc::getsid(pid), pid);
+            assert_ne!(libc::getpgid(pid), libc::getpgid(0));
+
+            libc::kill(pid, libc::SIGHUP);
+            std::thread::sleep(Duration::from_millis(100));
+            assert_eq!(libc::waitpid(pid, std::ptr::null_mut(), libc::WNOHANG), 0);
+
+            libc::kill(pid, libc::SIGKILL);
+            libc::waitpid(pid, std::ptr::null_mut(), 0);
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn resource_usage_measures_cpu_over_an_interval() {
+        let mut child = Command::new("sh").args(["-c", "while :; do :; done"]).spawn().unwrap();
+        // sysinfo reports no CPU for a process that has not yet accrued any CPU time.
+        std::thread::sleep(Duration::from_millis(300));
+        let usage = measure_usage(child.id() as i32);
+        child.kill().ok();
+        child.wait().ok();
+
+        let usage = usage.expect("a live process should report usage");
+        assert!(usage.cpu_percent > 10.0, "busy loop reported {}% CPU", usage.cpu_percent);
+        assert!(usage.memory_bytes > 0);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn is_process_alive_asks_the_installed_driver() {
+        struct FixedDriver(i32);
+        impl ProcessDriver for FixedDriver {
+            fn spawn(&self, _service: &ManagedService, _log: &Path) -> Result<i32, AppError> {
+                Ok(self.0)
+            }
+            fn is_running(&self, _service: &ManagedService, pid: i32) -> bool {
+                pid == self.0
+            }
+            fn is_running_by_signature(&self, _service: &ManagedService) -> Option<i32> {
+                None
+            }
+            fn signal(&self, _: &ManagedService, _: i32, _: bool) -> Result<bool, AppError> {
+                Ok(false)
+            }
+            fn health_port_open(&self, _service: &ManagedService) -> bool {
+                false
+            }
+        }
+
+        let project = TestProject::new();
+        let svc = service(&project);
+        let _guard = install_driver(Box::new(FixedDriver(77)));
+        assert!(is_process_alive(&svc, 77));
+        assert!(!is_process_alive(&svc, 78));
+    }
+
+    #[test]
+    fn graceful_stops_are_reported_as_forced_without_sigterm() {
+        assert_eq!(
+            stopped_outcome(Some(7), 1, false, true),
+            StopOutcome::Stopped { pid: 7, forced: false }
+        );
+        assert_eq!(
+            stopped_outcome(None, 2, true, true),
+            StopOutcome::TerminatedByName { count: 2, forced: true }
+        );
+        assert_eq!(
+            stopped_outcome(Some(7), 1, false, false),
+            StopOutcome::ForceTerminated { pid: Some(7), count: 1 }
+        );
+        assert_eq!(
+            stopped_outcome(Some(7), 1, true, false),
+            StopOutcome::Stopped { pid: 7, forced: true }
+        );
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn windows_has_no_graceful_stop() {
+        assert!(!GRACEFUL_STOP_SUPPORTED);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn unix_supports_graceful_stop() {
+        assert!(GRACEFUL_STOP_SUPPORTED);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn poisoned_driver_lock_is_recovered() {
+        let project = TestProject::new();
+        let svc = service(&project);
+        let _ = std::thread::spawn(|| {
+            let _guard = DRIVER.write().unwrap();
+            panic!("poison the driver lock");
+        })
+        .join();
+        assert!(DRIVER.is_poisoned());
+
+        assert!(!with_driver(|driver| driver.is_running(&svc, i32::MAX)));
+        assert!(!DRIVER.is_poisoned());
+        drop(install_driver(Box::new(SystemProcessDriver::new())));
+    }
+
+    #[test]
+    fn poisoned_process_table_lock_is_recovered() {
+        let driver = std::sync::Arc::new(SystemProcessDriver::new());
+        let poisoner = std::sync::Arc::clone(&driver);
+        let _ = std::thread::spawn(move || {
+            poisoner.with_state(|_| panic!("poison the process table lock"));
+        })
+        .join();
+
+        assert_eq!(driver.with_state(|state| state.last_full_refresh), None);
+        assert!(!driver.state.is_poisoned());
+    }
+
+    /// Driver that knows when its process is ready and polls the predicate itself.
+    struct ReadySignalDriver;
+
+    impl ProcessDriver for ReadySignalDriver {
+        fn spawn(&self, _service: &ManagedService, _log_path: &Path) -> Result<i32, AppError> {
+            Ok(77)
+        }
+
+        fn spawn_with_ready_signal(
+            &self,
+            service: &ManagedService,
+            log_path: &Path,
+            ready: &dyn Fn(i32) -> bool,
+        ) -> Result<(i32, bool), AppError> {
+            let pid = self.spawn(service, log_path)?;
+            Ok((pid, (0..5).any(|_| ready(pid))))
+        }
+
+        fn is_running(&self, _service: &ManagedService, _pid: i32) -> bool {
+            false
+        }
+
+        fn is_running_by_signature(&self, _service: &ManagedService) -> Option<i32> {
+            None
+        }
+
+        fn signal(&self, _: &ManagedService, _: i32, _: bool) -> Result<bool, AppError> {
+            Ok(false)
+        }
+
+        fn health_port_open(&self, _service: &ManagedService) -> bool {
+            false
+        }
+    }
+
+    /// Driver whose spawn races with another command that records a live PID first.
+    struct RacingDriver {
+        signalled: std::sync::Arc<Mutex<Vec<i32>>>,
+    }
+
+    impl ProcessDriver for RacingDriver {
+        fn spawn(&self, service: &ManagedService, _log_path: &Path) -> Result<i32, AppError> {
+            write_pid(service, 500)?;
+            Ok(600)
+        }
+
+        fn is_running(&self, _service: &ManagedService, pid: i32) -> bool {
+            pid == 500 || pid == 600
+        }
+
+        fn is_running_by_signature(&self, _service: &ManagedService) -> Option<i32> {
+            Some(700)
+        }
+
+        fn signal(&self, _: &ManagedService, pid: i32, _: bool) -> Result<bool, AppError> {
+            self.signalled.lock().unwrap().push(pid);
+            Ok(true)
+        }
+
+        fn health_port_open(&self, _service: &ManagedService) -> bool {
+            false
+        }
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn concurrent_pid_writes_keep_the_first_live_process() {
+        let project = TestProject::new();
+        let svc = service(&project);
+        let signalled = std::sync::Arc::new(Mutex::new(Vec::new()));
+        let driver = RacingDriver { signalled: std::sync::Arc::clone(&signalled) };
+        let _guard = install_driver(Box::new(driver));
+
+        // The concurrent PID wins; the duplicate this start spawned is stopped.
+        assert_eq!(start_service(&svc).unwrap(), StartOutcome::AlreadyRunning { pid: 500 });
+        assert_eq!(read_pid(&svc).unwrap(), Some(500));
+        assert_eq!(*signalled.lock().unwrap(), [600]);
+
+        // A signature match does not overwrite a live PID written after the status read.
+        write_pid(&svc, 500).unwrap();
+        assert_eq!(write_pid_unless_live(&svc, 700).unwrap(), 500);
+        assert_eq!(read_pid(&svc).unwrap(), Some(500));
+        write_pid(&svc, i32::MAX).unwrap();
+        assert_eq!(write_pid_unless_live(&svc, 700).unwrap(), 700);
+        assert_eq!(read_pid(&svc).unwrap(), Some(700));
+    }
+
+    /// Driver whose signature matches change between the prompt and the kill: PID 3 appears
+    /// and PID 2 is recycled by an unrelated process.
+    struct ShiftingMatchesDriver {
+        scans: Mutex<usize>,
+        signalled: std::sync::Arc<Mutex<Vec<i32>>>,
+    }
+
+    impl ProcessDriver for ShiftingMatchesDriver {
+        fn spawn(&self, _service: &ManagedService, _log_path: &Path) -> Result<i32, AppError> {
+            Ok(1)
+        }
+
+        fn is_running(&self, _service: &ManagedService, _pid: i32) -> bool {
+            false
+        }
+
+        fn is_running_by_signature(&self, _service: &ManagedService) -> Option<i32> {
+            None
+        }
+
+        fn processes_by_signature(&self, _service: &ManagedService) -> Vec<SignatureMatch> {
+            let mut scans = self.scans.lock().unwrap();
+            *scans += 1;
+            let pids: &[i32] = if *scans == 1 { &[1, 2] } else { &[1, 3] };
+            pids.iter().map(|&pid| SignatureMatch { pid, command: "dummy".into() }).collect()
+        }
+
+        fn signal(&self, _: &ManagedService, pid: i32, _: bool) -> Result<bool, AppError> {
+            self.signalled.lock().unwrap().push(pid);
+            Ok(pid != 2)
+        }
+
+        fn health_port_open(&self, _service: &ManagedService) -> bool {
+            false
+        }
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn forced_stop_signals_only_the_confirmed_processes() {
+        let project = TestProject::new();
+        let svc = service(&project);
+        let signalled = std::sync::Arc::new(Mutex::new(Vec::new()));
+        let driver = ShiftingMatchesDriver {
+            scans: Mutex::new(0),
+            signalled: std::sync::Arc::clone(&signalled),
+        };
+        let _guard = install_driver(Box::new(driver));
+
+        let mut shown = Vec::new();
+        let outcome = stop_service_confirmed(&svc, true, &mut |matches| {
+            shown.extend(matches.iter().map(|found| found.pid));
+            true
+        })
+        .unwrap();
+
+        assert_eq!(shown, [1, 2]);
+        // PID 3 was never shown; PID 2 no longer matches, so only PID 1 counts as killed.
+        assert_eq!(*signalled.lock().unwrap(), [1, 2]);
+        assert!(matches!(outcome, StopOutcome::TerminatedByName { count: 1, .. }), "{outcome:?}");
+    }
+
+    /// Driver whose spawn is slow enough for a second start to arrive mid-spawn.
+    struct SlowSpawnDriver {
+        spawned: std::sync::Arc<Mutex<Vec<i32>>>,
+    }
+
+    impl ProcessDriver for SlowSpawnDriver {
+        fn spawn(&self, _service: &ManagedService, _log_path: &Path) -> Result<i32, AppError> {
+            std::thread::sleep(Duration::from_millis(100));
+            let mut spawned = self.spawned.lock().unwrap();
+            let pid = 900 + spawned.len() as i32;
+            spawned.push(pid);
+            Ok(pid)
+        }
+
+        fn is_running(&self, _service: &ManagedService, pid: i32) -> bool {
+            self.spawned.lock().unwrap().contains(&pid)
+        }
+
+        fn is_running_by_signature(&self, _service: &ManagedService) -> Option<i32> {
+            None
+        }
+
+        fn signal(&self, _: &ManagedService, _: i32, _: bool) -> Result<bool, AppError> {
+            Ok(true)
+        }
+
+        fn health_port_open(&self, _service: &ManagedService) -> bool {
+            false
+        }
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn concurrent_starts_spawn_the_service_once() {
+        let project = TestProject::new();
+        let svc = service(&project);
+        let spawned = std::sync::Arc::new(Mutex::new(Vec::new()));
+        let driver = SlowSpawnDriver { spawned: std::sync::Arc::clone(&spawned) };
+        let _guard = install_driver(Box::new(driver));
+
+        let outcomes: Vec<StartOutcome> = std::thread::scope(|scope| {
+            let starts: Vec<_> = (0..2).map(|_| scope.spawn(|| start_service(&svc))).collect();
+            starts.into_iter().map(|start| start.join().unwrap().unwrap()).collect()
+        });
+
+        assert_eq!(spawned.lock().unwrap().len(), 1, "only one start may spawn");
+        let started = outcomes.iter().filter(|o| matches!(o, StartOutcome::Started { .. })).count();
+        assert_eq!(started, 1);
+        assert!(outcomes.contains(&StartOutcome::AlreadyRunning { pid: 900 }));
+        assert!(svc.lock_path().unwrap().exists());
+    }
+
+    /// Driver whose status queries finish in reverse order of the services' PIDs.
+    struct StaggeredDriver;
+
+    impl ProcessDriver for StaggeredDriver {
+        fn spawn(&self, _service: &ManagedService, _log_path: &Path) -> Result<i32, AppError> {
+            Ok(1)
+        }
+
+        fn is_running(&self, _service: &ManagedService, pid: i32) -> bool {
+            std::thread::sleep(Duration::from_millis(10 * (6 - pid as u64)));
+            pid % 2 == 1
+        }
+
+        fn is_running_by_signature(&self, _service: &ManagedService) -> Option<i32> {
+            None
+        }
+
+        fn signal(&self, _: &ManagedService, _: i32, _: bool) -> Result<bool, AppError> {
+            Ok(false)
+        }
+
+        fn health_port_open(&self, _service: &ManagedService) -> bool {
+            false
+        }
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn concurrent_status_queries_keep_service_order() {
+        let project = TestProject::new();
+        let services: Vec<_> = (1..=5)
+            .map(|pid| {
+                let svc =
+                    service(&project).with_overrides(&crate::core::services::ServiceOverrides {
+                        state_stem: Some(format!("svc-{pid}")),
+                        ..Default::default()
+                    });
+                write_pid(&svc, pid).unwrap();
+                svc
+            })
+            .collect();
+        let _guard = install_driver(Box::new(StaggeredDriver));
+
+        let running = |pid| StatusOutcome::Running { pid };
+        let stopped = StatusOutcome::NotRunning;
+        let expected = [running(1), stopped, running(3), stopped, running(5)];
+        for count in [1, 2, 5] {
+            assert_eq!(status_services(&services, count).unwrap(), expected, "count {count}");
+            // Re-record the stale PIDs that the query cleared.
+            write_pid(&services[1], 2).unwrap();
+            write_pid(&services[3], 4).unwrap();
+        }
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn drivers_can_confirm_readiness_while_spawning() {
+        let project = TestProject::new();
+        let svc = service(&project);
+        let _guard = install_driver(Box::new(ReadySignalDriver));
+
+        let polls = std::cell::Cell::new(0);
+        let ready = |pid: i32| {
+            assert_eq!(pid, 77);
+            polls.set(polls.get() + 1);
+            polls.get() == 3
+        };
+        let outcome = start_service_with_ready_signal(&svc, Some(&ready)).unwrap();
+        assert_eq!(outcome, StartOutcome::Started { pid: 77, ready: true });
+        assert_eq!(polls.get(), 3);
+
+        remove_pid(&svc).unwrap();
+        let outcome = start_service(&svc).unwrap();
+        assert_eq!(outcome, StartOutcome::Started { pid: 77, ready: false });
+    }
 }
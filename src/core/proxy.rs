@@ -0,0 +1,306 @@
+//! Minimal OpenAI-compatible reverse proxy that routes chat completions by requested model.
+
+use crate::core::services::ManagedService;
+use crate::core::{health, http};
+use crate::error::AppError;
+use reqwest::blocking::Client;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::time::Duration;
+
+const CHAT_COMPLETIONS_PATH: &str = "/v1/chat/completions";
+const RELAY_BUFFER_BYTES: usize = 8 * 1024;
+/// Overall deadline for buffered (non-streaming) completions.
+const NON_STREAMING_TIMEOUT_SECS: u64 = 120;
+/// Largest request body accepted; bigger ones get 413 before anything is allocated.
+const MAX_BODY_BYTES: usize = 32 * 1024 * 1024;
+/// How long a client may stall while sending its request, since connections are served one at a
+/// time and an idle one would otherwise block every other caller.
+const CLIENT_READ_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A managed runtime the proxy can forward to, paired with the model it is configured to serve.
+#[derive(Debug, Clone)]
+pub struct ProxyBackend {
+    pub service: ManagedService,
+    pub model: String,
+}
+
+struct ProxyRequest {
+    method: String,
+    path: String,
+    /// Declared `Content-Length`; the body is left unread when it exceeds `MAX_BODY_BYTES`.
+    content_length: usize,
+    body: Vec<u8>,
+}
+
+/// Pick the backend for `model`: an exact configured-model match wins, otherwise Hugging Face
+/// style repository ids (`org/name`) go to MLX and everything else to Ollama.
+pub fn route_for_model<'a>(backends: &'a [ProxyBackend], model: &str) -> Option<&'a ProxyBackend> {
+    if let Some(backend) = backends.iter().find(|backend| backend.model == model) {
+        return Some(backend);
+    }
+    let preferred = if model.contains('/') { "mlx" } else { "ollama" };
+    backends.iter().find(|backend| backend.service.name == preferred)
+}
+
+/// Accept connections one at a time and forward each chat completion to its backend.
+pub fn serve(listener: &TcpListener, backends: &[ProxyBackend]) -> Result<(), AppError> {
+    let client = proxy_client()?;
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                if let Err(err) = handle_connection(stream, backends, &client) {
+                    eprintln!("proxy: {err}");
+                }
+            }
+            Err(err) => eprintln!("proxy: failed to accept connection: {err}"),
+        }
+    }
+    Ok(())
+}
+
+fn proxy_client() -> Result<Client, AppError> {
+    // Streaming responses can legitimately run for minutes, so the overall timeout is applied
+    // per request in `upstream_timeout` rather than on the client.
+    http::client(None).map_err(|e| AppError::config_error(format!("Client build error: {e}")))
+}
+
+/// Streamed completions are unbounded in length, so only buffered requests get a deadline.
+fn upstream_timeout(body: &serde_json::Value) -> Option<Duration> {
+    if body["stream"].as_bool() == Some(true) {
+        None
+    } else {
+        Some(Duration::from_secs(NON_STREAMING_TIMEOUT_SECS))
+    }
+}
+
+fn handle_connection(
+    stream: TcpStream,
+    backends: &[ProxyBackend],
+    client: &Client,
+) -> Result<(), AppError> {
+    stream.set_read_timeout(Some(CLIENT_READ_TIMEOUT))?;
+    let mut reader = BufReader::new(stream);
+    let request = read_request(&mut reader)?;
+    let mut stream = reader.into_inner();
+
+    if request.content_length > MAX_BODY_BYTES {
+        return write_error(
+            &mut stream,
+            "413 Payload Too Large",
+            &format!("request body exceeds {MAX_BODY_BYTES} bytes"),
+        );
+    }
+
+    if request.method != "POST" || request.path != CHAT_COMPLETIONS_PATH {
+        return write_error(
+            &mut stream,
+            "404 Not Found",
+            "only POST /v1/chat/completions is proxied",
+        );
+    }
+
+    let json = serde_json::from_slice::<serde_json::Value>(&request.body).ok();
+    let model = json.as_ref().and_then(|json| json["model"].as_str().map(str::to_string));
+    let Some(model) = model else {
+        return write_error(&mut stream, "400 Bad Request", "request body must include a model");
+    };
+    let Some(backend) = route_for_model(backends, &model) else {
+        return write_error(&mut stream, "404 Not Found", &format!("no backend for model {model}"));
+    };
+
+    let headers = match http::service_headers(&backend.service) {
+        Ok(headers) => headers,
+        Err(err) => return write_error(&mut stream, "500 Internal Server Error", &err.to_string()),
+    };
+    let mut upstream = client
+        .post(health::chat_completions_url(&backend.service))
+        .headers(headers)
+        .header(reqwest::header::CONTENT_TYPE, "application/json");
+    if let Some(timeout) = json.as_ref().and_then(upstream_timeout) {
+        upstream = upstream.timeout(timeout);
+    }
+    let upstream = upstream.body(request.body).send();
+    let mut upstream = match upstream {
+        Ok(response) => response,
+        Err(err) => {
+            return write_error(&mut stream, "502 Bad Gateway", &format!("upstream error: {err}"));
+        }
+    };
+
+    let content_type = upstream
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or("application/json")
+        .to_string();
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {content_type}\r\nTransfer-Encoding: chunked\r\nConnection: close\r\n\r\n",
+        upstream.status()
+    )?;
+
+    // Relay the body chunk by chunk so server-sent events reach the caller unmodified.
+    let mut buffer = [0u8; RELAY_BUFFER_BYTES];
+    loop {
+        let read = upstream.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        write!(stream, "{read:x}\r\n")?;
+        stream.write_all(&buffer[..read])?;
+        stream.write_all(b"\r\n")?;
+        stream.flush()?;
+    }
+    stream.write_all(b"0\r\n\r\n")?;
+    stream.flush()?;
+    Ok(())
+}
+
+fn read_request(reader: &mut BufReader<TcpStream>) -> Result<ProxyRequest, AppError> {
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let path = parts.next().unwrap_or_default().to_string();
+
+    let mut content_length = 0usize;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':')
+            && name.trim().eq_ignore_ascii_case("content-length")
+        {
+            content_length = value.trim().parse().map_err(|_| {
+                io::Error::new(io::ErrorKind::InvalidData, "invalid Content-Length header")
+            })?;
+        }
+    }
+
+    let mut body = Vec::new();
+    if content_length <= MAX_BODY_BYTES {
+        body.resize(content_length, 0);
+        reader.read_exact(&mut body)?;
+    }
+    Ok(ProxyRequest { method, path, content_length, body })
+}
+
+fn write_error(stream: &mut TcpStream, status: &str, message: &str) -> Result<(), AppError> {
+    let body = serde_json::json!({ "error": { "message": message } }).to_string();
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )?;
+    stream.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::Config;
+    use crate::core::services;
+    use std::thread;
+
+    fn backends(cfg: &Config) -> Vec<ProxyBackend> {
+        vec![
+            ProxyBackend {
+                service: services::create_ollama_service(&cfg.ollama_server),
+                model: cfg.ollama_server.model.clone(),
+            },
+            ProxyBackend {
+                service: services::create_mlx_service(&cfg.mlx_server),
+                model: cfg.mlx_server.model.clone(),
+            },
+        ]
+    }
+
+    #[test]
+    fn route_prefers_exact_model_then_falls_back_by_shape() {
+        let cfg = Config::default();
+        let backends = backends(&cfg);
+        assert_eq!(route_for_model(&backends, &cfg.mlx_server.model).unwrap().service.name, "mlx");
+        assert_eq!(route_for_model(&backends, "llama3.2:3b").unwrap().service.name, "ollama");
+        assert_eq!(route_for_model(&backends, "org/other-model").unwrap().service.name, "mlx");
+        assert_eq!(route_for_model(&backends, "qwen2.5:7b").unwrap().service.name, "ollama");
+    }
+
+    #[test]
+    fn only_non_streaming_requests_get_an_overall_timeout() {
+        assert_eq!(upstream_timeout(&serde_json::json!({ "stream": true })), None);
+        assert_eq!(
+            upstream_timeout(&serde_json::json!({ "stream": false })),
+            Some(Duration::from_secs(NON_STREAMING_TIMEOUT_SECS))
+        );
+        assert!(upstream_timeout(&serde_json::json!({ "model": "m" })).is_some());
+    }
+
+    #[test]
+    fn oversized_bodies_are_rejected_without_being_read() {
+        let proxy = TcpListener::bind("127.0.0.1:0").unwrap();
+        let proxy_port = proxy.local_addr().unwrap().port();
+        let proxy_thread = thread::spawn(move || {
+            let (stream, _) = proxy.accept().unwrap();
+            handle_connection(stream, &[], &proxy_client().unwrap()).unwrap();
+        });
+
+        let mut client = TcpStream::connect(("127.0.0.1", proxy_port)).unwrap();
+        write!(
+            client,
+            "POST {CHAT_COMPLETIONS_PATH} HTTP/1.1\r\nContent-Length: 99999999999\r\n\r\n"
+        )
+        .unwrap();
+        let mut response = String::new();
+        client.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 413 Payload Too Large"), "{response}");
+
+        proxy_thread.join().unwrap();
+    }
+
+    #[test]
+    fn proxy_forwards_body_to_matching_backend() {
+        let upstream = TcpListener::bind("127.0.0.1:0").unwrap();
+        let mut cfg = Config::default();
+        cfg.mlx_server.port = upstream.local_addr().unwrap().port();
+        let backends = backends(&cfg);
+
+        let upstream_thread = thread::spawn(move || {
+            let (stream, _) = upstream.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let request = read_request(&mut reader).unwrap();
+            assert_eq!(request.path, CHAT_COMPLETIONS_PATH);
+            let body = br#"{"choices":[{"message":{"content":"proxied"}}]}"#;
+            let mut stream = reader.into_inner();
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n",
+                body.len()
+            )
+            .unwrap();
+            stream.write_all(body).unwrap();
+        });
+
+        let proxy = TcpListener::bind("127.0.0.1:0").unwrap();
+        let proxy_port = proxy.local_addr().unwrap().port();
+        let proxy_thread = thread::spawn(move || {
+            let (stream, _) = proxy.accept().unwrap();
+            handle_connection(stream, &backends, &proxy_client().unwrap()).unwrap();
+        });
+
+        let payload = serde_json::json!({ "model": cfg.mlx_server.model, "messages": [] });
+        let response = Client::new()
+            .post(format!("http://127.0.0.1:{proxy_port}{CHAT_COMPLETIONS_PATH}"))
+            .json(&payload)
+            .send()
+            .unwrap();
+        assert!(response.status().is_success());
+        let body: serde_json::Value = response.json().unwrap();
+        assert_eq!(body["choices"][0]["message"]["content"], "proxied");
+
+        proxy_thread.join().unwrap();
+        upstream_thread.join().unwrap();
+    }
+}
@@ -1,104 +1,405 @@
-use crate::core::config::{Config, MlxServerConfig, OllamaServerConfig};
+use crate::core::config::{
+    Config, DEFAULT_MLX_PORT, DEFAULT_OLLAMA_PORT, MlxServerConfig, OllamaServerConfig, ReadyMethod,
+};
+use crate::core::health::{self, ReadinessProbe};
 use crate::core::{config, paths, process};
 use crate::error::AppError;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
 use std::path::PathBuf;
 
+/// Which managed runtime a command or library call targets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServiceType {
+    Ollama,
+    Mlx,
+}
+
 #[derive(Debug, Clone)]
 pub struct ManagedService {
     pub name: &'static str,
+    /// The runtime this service runs, for behaviour that differs between backends.
+    pub service_type: ServiceType,
     pub host: String,
     pub port: u16,
+    /// `http` or `https`, used to build API URLs.
+    pub scheme: String,
     pub command: Vec<String>,
-    pub log_filename: &'static str,
-    pub pid_filename: &'static str,
-    pub config_filename: &'static str,
+    pub log_filename: String,
+    pub pid_filename: String,
+    pub config_filename: String,
     pub env: HashMap<String, String>,
+    /// Extra HTTP headers for API requests, before `${VAR}` expansion.
+    pub headers: BTreeMap<String, String>,
+    /// Configured readiness endpoint; `None` uses the built-in inference probe.
+    pub ready_check: Option<ReadyCheck>,
+}
+
+/// A user-configured readiness endpoint, for backends the built-in probe does not fit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReadyCheck {
+    pub method: ReadyMethod,
+    pub path: String,
+}
+
+impl ReadyCheck {
+    /// `None` when neither setting is configured; otherwise missing parts take the defaults of
+    /// `POST /v1/chat/completions`.
+    fn from_config(path: Option<&str>, method: Option<ReadyMethod>) -> Option<Self> {
+        if path.is_none() && method.is_none() {
+            return None;
+        }
+        Some(Self {
+            method: method.unwrap_or(ReadyMethod::Post),
+            path: path.unwrap_or(HealthEndpoint::OpenAiChat.path()).to_string(),
+        })
+    }
+}
+
+/// Adjustments applied on top of a config-derived service, for tools that embed fusion's process
+/// management and need a variant of the configured server.
+#[derive(Debug, Clone, Default)]
+pub struct ServiceOverrides {
+    /// Arguments appended to the server command.
+    pub extra_args: Vec<String>,
+    /// Environment entries, replacing config-derived values on conflict.
+    pub env: Vec<(String, String)>,
+    /// Stem for the log, PID, and sidecar files, e.g. `ollama-ci` for `ollama-ci.log`.
+    pub state_stem: Option<String>,
+}
+
+/// An inference endpoint that can prove a service is healthy, in the order they are tried.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HealthEndpoint {
+    /// OpenAI-compatible `/v1/chat/completions`, served by both runtimes.
+    OpenAiChat,
+    /// Ollama's native `/api/chat`, for builds whose OpenAI shim is missing.
+    OllamaChat,
+}
+
+impl HealthEndpoint {
+    pub fn path(self) -> &'static str {
+        match self {
+            HealthEndpoint::OpenAiChat => "/v1/chat/completions",
+            HealthEndpoint::OllamaChat => "/api/chat",
+        }
+    }
 }
 
 impl ManagedService {
+    /// Endpoints for health and readiness checks; later entries are fallbacks for a 404.
+    pub fn health_endpoints(&self) -> &'static [HealthEndpoint] {
+        match self.service_type {
+            ServiceType::Ollama => &[HealthEndpoint::OpenAiChat, HealthEndpoint::OllamaChat],
+            ServiceType::Mlx => &[HealthEndpoint::OpenAiChat],
+        }
+    }
+
     pub fn log_path(&self) -> Result<PathBuf, AppError> {
-        paths::service_state_dir(self.name).map(|dir| dir.join(self.log_filename))
+        paths::service_state_dir(self.name).map(|dir| dir.join(&self.log_filename))
+    }
+
+    /// The server's stderr, kept apart from [`Self::log_path`] as e.g. `ollama.err.log`.
+    pub fn stderr_log_path(&self) -> Result<PathBuf, AppError> {
+        self.log_path().map(|path| path.with_extension("err.log"))
     }
 
     pub fn pid_path(&self) -> Result<PathBuf, AppError> {
-        paths::service_state_dir(self.name).map(|dir| dir.join(self.pid_filename))
+        paths::service_state_dir(self.name).map(|dir| dir.join(&self.pid_filename))
+    }
+
+    /// Advisory lock file that serializes lifecycle changes, e.g. `ollama.pid.lock`.
+    pub fn lock_path(&self) -> Result<PathBuf, AppError> {
+        self.pid_path().map(|path| path.with_extension("pid.lock"))
     }
 
     pub fn config_path(&self) -> Result<PathBuf, AppError> {
-        paths::service_state_dir(self.name).map(|dir| dir.join(self.config_filename))
+        paths::service_state_dir(self.name).map(|dir| dir.join(&self.config_filename))
+    }
+
+    /// Root URL of the service's HTTP API, e.g. `http://[::1]:8080`.
+    pub fn base_url(&self) -> String {
+        format!("{}://{}", self.scheme, config::format_host_port(&self.host, self.port))
+    }
+
+    /// Absolute URL for `path` (which must start with `/`) on the service's HTTP API.
+    pub fn url(&self, path: &str) -> String {
+        format!("{}{path}", self.base_url())
+    }
+
+    /// Render the command as a POSIX shell-quoted line suitable for copy-pasting.
+    pub fn command_line(&self) -> String {
+        self.command.iter().map(|arg| shell_quote(arg)).collect::<Vec<_>>().join(" ")
+    }
+
+    /// Merge one-off environment overrides, replacing config-derived values on conflict.
+    pub fn merge_env(&mut self, overrides: &[(String, String)]) {
+        for (key, value) in overrides {
+            self.env.insert(key.clone(), value.clone());
+        }
+    }
+
+    /// Apply `overrides`, leaving anything they do not mention as configured.
+    pub fn with_overrides(mut self, overrides: &ServiceOverrides) -> Self {
+        self.command.extend(overrides.extra_args.iter().cloned());
+        self.merge_env(&overrides.env);
+        if let Some(stem) = &overrides.state_stem {
+            self.log_filename = format!("{stem}.log");
+            self.pid_filename = format!("{stem}.pid");
+            self.config_filename = format!("{stem}.config");
+        }
+        self
+    }
+
+    /// Keep `other`'s log, PID, and sidecar names, e.g. for a one-off `--port` launch that later
+    /// commands should still find under the configured port.
+    pub fn use_state_files_of(&mut self, other: &ManagedService) {
+        self.log_filename = other.log_filename.clone();
+        self.pid_filename = other.pid_filename.clone();
+        self.config_filename = other.config_filename.clone();
+    }
+
+    /// Environment entries sorted by key as shell-quoted `KEY=value` assignments.
+    pub fn env_assignments(&self) -> Vec<String> {
+        let mut env: Vec<_> = self.env.iter().collect();
+        env.sort();
+        env.into_iter().map(|(key, value)| format!("{key}={}", shell_quote(value))).collect()
+    }
+
+    /// Environment entries sorted by key as `export KEY="value"` lines for sourcing in a shell.
+    pub fn env_exports(&self) -> Vec<String> {
+        let mut env: Vec<_> = self.env.iter().collect();
+        env.sort();
+        env.into_iter()
+            .map(|(key, value)| format!("export {key}={}", double_quote(value)))
+            .collect()
+    }
+}
+
+/// Wrap `value` in double quotes, escaping the characters a shell expands inside them.
+fn double_quote(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        if matches!(c, '"' | '\\' | '$' | '`') {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Quote `value` for a POSIX shell, leaving simple words untouched.
+pub fn shell_quote(value: &str) -> String {
+    let is_plain = !value.is_empty()
+        && value.chars().all(|c| c.is_ascii_alphanumeric() || "-_./:=@%+,".contains(c));
+    if is_plain { value.to_string() } else { format!("'{}'", value.replace('\'', r"'\''")) }
+}
+
+/// State file name for a service: `ollama.log` on the default port, `ollama-11500.log` otherwise,
+/// so instances on different ports do not clobber each other's files.
+fn state_filename(name: &str, port: u16, default_port: u16, extension: &str) -> String {
+    if port == default_port {
+        format!("{name}.{extension}")
+    } else {
+        format!("{name}-{port}.{extension}")
     }
 }
 
 pub fn create_ollama_service(cfg: &OllamaServerConfig) -> ManagedService {
+    let host = config::normalise_host(&cfg.host).to_string();
     let mut env_map = config::server_env(&cfg.extra, "OLLAMA_");
-    env_map.insert("OLLAMA_HOST".into(), config::format_host_port(&cfg.host, cfg.port));
+    env_map.insert("OLLAMA_HOST".into(), config::format_host_port(&host, cfg.port));
 
     ManagedService {
         name: "ollama",
-        host: cfg.host.clone(),
+        service_type: ServiceType::Ollama,
+        host,
         port: cfg.port,
-        command: vec!["ollama".into(), "serve".into()],
-        log_filename: "ollama.log",
-        pid_filename: "ollama.pid",
-        config_filename: "ollama.config",
+        scheme: cfg.scheme.clone(),
+        command: ["ollama".to_string(), "serve".to_string()]
+            .into_iter()
+            .chain(cfg.extra_args.iter().cloned())
+            .collect(),
+        log_filename: state_filename("ollama", cfg.port, DEFAULT_OLLAMA_PORT, "log"),
+        pid_filename: state_filename("ollama", cfg.port, DEFAULT_OLLAMA_PORT, "pid"),
+        config_filename: state_filename("ollama", cfg.port, DEFAULT_OLLAMA_PORT, "config"),
         env: env_map,
+        headers: cfg.headers.clone(),
+        ready_check: ReadyCheck::from_config(cfg.ready_path.as_deref(), cfg.ready_method),
     }
 }
 
 pub fn create_mlx_service(cfg: &MlxServerConfig) -> ManagedService {
+    let host = config::normalise_host(&cfg.host).to_string();
     let env_map = config::server_env(&cfg.extra, "MLX_");
 
     ManagedService {
         name: "mlx",
-        host: cfg.host.clone(),
+        service_type: ServiceType::Mlx,
+        host: host.clone(),
         port: cfg.port,
-        command: vec![
-            "mlx_lm.server".into(),
-            "--model".into(),
-            cfg.model.clone(),
-            "--host".into(),
-            cfg.host.clone(),
-            "--port".into(),
-            cfg.port.to_string(),
-        ],
-        log_filename: "mlx.log",
-        pid_filename: "mlx.pid",
-        config_filename: "mlx.config",
+        scheme: cfg.scheme.clone(),
+        command: mlx_command(cfg, &host, cfg.port),
+        log_filename: state_filename("mlx", cfg.port, DEFAULT_MLX_PORT, "log"),
+        pid_filename: state_filename("mlx", cfg.port, DEFAULT_MLX_PORT, "pid"),
+        config_filename: state_filename("mlx", cfg.port, DEFAULT_MLX_PORT, "config"),
         env: env_map,
+        headers: cfg.headers.clone(),
+        ready_check: ReadyCheck::from_config(cfg.ready_path.as_deref(), cfg.ready_method),
     }
 }
 
+fn mlx_command(cfg: &MlxServerConfig, host: &str, port: u16) -> Vec<String> {
+    let mut command = vec![
+        "mlx_lm.server".into(),
+        "--model".into(),
+        cfg.model.clone(),
+        "--host".into(),
+        host.to_string(),
+        "--port".into(),
+        port.to_string(),
+    ];
+    command.extend(cfg.extra_args.iter().cloned());
+    command
+}
+
 pub fn load_ollama_service(cfg: &OllamaServerConfig) -> Result<ManagedService, AppError> {
     let mut service = create_ollama_service(cfg);
-    if let Some((host, port)) = process::read_config(&service)? {
-        service.host = host.clone();
+    adopt_recorded_instance(&mut service)?;
+    if let Some(runtime) = process::read_config(&service)? {
+        let host = config::normalise_host(&runtime.host).to_string();
+        let port = runtime.port;
+        if !restore_runtime(&mut service, runtime) {
+            service.env.insert("OLLAMA_HOST".into(), config::format_host_port(&host, port));
+        }
+        service.host = host;
         service.port = port;
-        service.env.insert("OLLAMA_HOST".into(), config::format_host_port(&host, port));
     }
     Ok(service)
 }
 
 pub fn load_mlx_service(cfg: &MlxServerConfig) -> Result<ManagedService, AppError> {
     let mut service = create_mlx_service(cfg);
-    if let Some((host, port)) = process::read_config(&service)? {
-        service.host = host.clone();
+    adopt_recorded_instance(&mut service)?;
+    if let Some(runtime) = process::read_config(&service)? {
+        let host = config::normalise_host(&runtime.host).to_string();
+        let port = runtime.port;
+        if !restore_runtime(&mut service, runtime) {
+            // Legacy sidecars only record host and port, so rebuild the command around them.
+            service.command = mlx_command(cfg, &host, port);
+        }
+        service.host = host;
         service.port = port;
-
-        // Rebuild command with updated host and port from runtime config
-        service.command = vec![
-            "mlx_lm.server".into(),
-            "--model".into(),
-            cfg.model.clone(),
-            "--host".into(),
-            host,
-            "--port".into(),
-            port.to_string(),
-        ];
     }
     Ok(service)
 }
 
+/// Point `service` at the state files of an instance started under another port when nothing
+/// was recorded under the configured one, so `down`/`ps` still find it after `config set`.
+fn adopt_recorded_instance(service: &mut ManagedService) -> Result<(), AppError> {
+    if service.config_path()?.exists() || service.pid_path()?.exists() {
+        return Ok(());
+    }
+    let dir = paths::service_state_dir(service.name)?;
+    let Ok(entries) = fs::read_dir(&dir) else {
+        return Ok(());
+    };
+    let mut stems: Vec<String> = entries
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .filter_map(|file| file.strip_suffix(".config").map(str::to_string))
+        .filter(|stem| is_port_stem(service.name, stem))
+        .filter(|stem| dir.join(format!("{stem}.pid")).exists())
+        .collect();
+    stems.sort();
+    if let Some(stem) = stems.first() {
+        service.log_filename = format!("{stem}.log");
+        service.pid_filename = format!("{stem}.pid");
+        service.config_filename = format!("{stem}.config");
+    }
+    Ok(())
+}
+
+/// Whether `stem` is a name [`state_filename`] produces for `name`: bare or port-suffixed.
+fn is_port_stem(name: &str, stem: &str) -> bool {
+    match stem.strip_prefix(name) {
+        Some("") => true,
+        Some(rest) => rest.strip_prefix('-').is_some_and(|port| port.parse::<u16>().is_ok()),
+        None => false,
+    }
+}
+
+/// Restore the exact command and environment recorded at start, so `down`/`ps` keep matching
+/// the running process even if the configuration changed since. Returns `false` for legacy
+/// sidecars that did not record them.
+fn restore_runtime(service: &mut ManagedService, runtime: process::RuntimeConfig) -> bool {
+    if runtime.command.is_empty() {
+        return false;
+    }
+    service.command = runtime.command;
+    service.env = runtime.env.into_iter().collect();
+    true
+}
+
+/// The service `up` starts for `service_type`, built from the current config.
+pub fn service_for_type(cfg: &Config, service_type: ServiceType) -> ManagedService {
+    match service_type {
+        ServiceType::Ollama => create_ollama_service(&cfg.ollama_server),
+        ServiceType::Mlx => create_mlx_service(&cfg.mlx_server),
+    }
+}
+
+/// Start `service_type` unless it is already running, wait until it answers inference, and return
+/// its final status. Runs the same config checks as `up`, but prints nothing and installs no
+/// signal handler, so programs embedding fusion can call it; a public-bind warning is logged.
+pub fn ensure_running(service_type: ServiceType) -> Result<process::StatusOutcome, AppError> {
+    let cfg = config::load_config()?;
+    if let Some(warning) = check_startable(&cfg, service_type, false)? {
+        tracing::warn!("{warning}");
+    }
+    let service = service_for_type(&cfg, service_type);
+    let model_name = match service_type {
+        ServiceType::Ollama => &cfg.ollama_server.model,
+        ServiceType::Mlx => &cfg.mlx_server.model,
+    };
+    let pid = match process::start_service(&service)? {
+        process::StartOutcome::Started { pid, .. }
+        | process::StartOutcome::AlreadyRunning { pid } => pid,
+        process::StartOutcome::PortInUse { port } => {
+            return Err(AppError::process_error(
+                service.name,
+                format!("port {port} is already in use by another process; not starting."),
+            ));
+        }
+    };
+    let timeout_secs = health::startup_timeout_secs();
+    health::wait_for_readiness(&service, pid, model_name, ReadinessProbe::Inference, timeout_secs)?;
+    process::status_service(&service)
+}
+
+/// The config checks `up` runs before starting `service_type`: port conflicts, colliding env keys
+/// under `--strict-env`, the MLX model, and a public bind host. The last is returned as a warning
+/// unless `allow_public_bind` acknowledges it or `--strict` makes it an error.
+pub fn check_startable(
+    cfg: &Config,
+    service_type: ServiceType,
+    allow_public_bind: bool,
+) -> Result<Option<String>, AppError> {
+    cfg.check_port_conflicts()?;
+    if config::is_strict_env() {
+        cfg.check_env_collisions()?;
+    }
+    if service_type == ServiceType::Mlx {
+        cfg.mlx_server.validate_model()?;
+    }
+    let (section, host) = match service_type {
+        ServiceType::Ollama => ("ollama_server", &cfg.ollama_server.host),
+        ServiceType::Mlx => ("mlx_server", &cfg.mlx_server.host),
+    };
+    config::check_public_bind(section, host, allow_public_bind, config::is_strict())
+}
+
 pub fn default_services(cfg: &Config) -> Result<Vec<ManagedService>, AppError> {
     Ok(vec![load_ollama_service(&cfg.ollama_server)?, load_mlx_service(&cfg.mlx_server)?])
 }
@@ -177,4 +478,214 @@ mod tests {
 
         process::remove_config(&configured).expect("config removal should succeed");
     }
+
+    #[test]
+    #[serial_test::serial]
+    fn extra_args_are_appended_to_commands() {
+        let _project = TestProject::new();
+        let mut cfg = config::Config::default();
+        cfg.mlx_server.extra_args = vec!["--trust-remote-code".into(), "--max-tokens".into()];
+        cfg.ollama_server.extra_args = vec!["--verbose".into()];
+
+        let mlx = create_mlx_service(&cfg.mlx_server);
+        assert_eq!(mlx.command[mlx.command.len() - 2..], ["--trust-remote-code", "--max-tokens"]);
+        assert_eq!(
+            create_ollama_service(&cfg.ollama_server).command,
+            ["ollama", "serve", "--verbose"]
+        );
+
+        process::write_config(&mlx).expect("config write should succeed");
+        let loaded = load_mlx_service(&cfg.mlx_server).expect("mlx service should load");
+        assert_eq!(loaded.command, mlx.command);
+        process::remove_config(&mlx).expect("config removal should succeed");
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn runtime_sidecar_restores_command_and_env_after_config_drift() {
+        let _project = TestProject::new();
+        let started_cfg = config::Config::default();
+        let mlx = create_mlx_service(&started_cfg.mlx_server);
+        let mut ollama = create_ollama_service(&started_cfg.ollama_server);
+        ollama.env.insert("CUDA_VISIBLE_DEVICES".into(), "1".into());
+        process::write_config(&mlx).unwrap();
+        process::write_config(&ollama).unwrap();
+
+        let mut drifted = config::Config::default();
+        drifted.mlx_server.model = "another/model".into();
+        drifted.mlx_server.extra_args = vec!["--trust-remote-code".into()];
+        drifted.ollama_server.extra.clear();
+
+        let loaded_mlx = load_mlx_service(&drifted.mlx_server).unwrap();
+        assert_eq!(loaded_mlx.command, mlx.command);
+        assert_eq!(loaded_mlx.env, mlx.env);
+
+        let loaded_ollama = load_ollama_service(&drifted.ollama_server).unwrap();
+        assert_eq!(loaded_ollama.command, ollama.command);
+        assert_eq!(loaded_ollama.env, ollama.env);
+
+        process::remove_config(&mlx).unwrap();
+        process::remove_config(&ollama).unwrap();
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn legacy_runtime_sidecar_still_loads() {
+        let _project = TestProject::new();
+        let cfg = config::Config::default();
+        let service = create_mlx_service(&cfg.mlx_server);
+        let path = service.config_path().unwrap();
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, "host=10.0.0.9\nport=6060\n").unwrap();
+
+        let loaded = load_mlx_service(&cfg.mlx_server).unwrap();
+        assert_eq!((loaded.host.as_str(), loaded.port), ("10.0.0.9", 6060));
+        assert!(loaded.command.contains(&"6060".to_string()));
+        process::remove_config(&service).unwrap();
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn merge_env_overrides_config_values() {
+        let _project = TestProject::new();
+        let mut cfg = config::Config::default();
+        cfg.ollama_server.extra.insert("num_parallel".into(), toml::Value::Integer(2));
+        let mut service = create_ollama_service(&cfg.ollama_server);
+
+        service.merge_env(&[
+            ("OLLAMA_NUM_PARALLEL".into(), "4".into()),
+            ("CUDA_VISIBLE_DEVICES".into(), "1".into()),
+        ]);
+        assert_eq!(service.env.get("OLLAMA_NUM_PARALLEL").unwrap(), "4");
+        assert_eq!(service.env.get("CUDA_VISIBLE_DEVICES").unwrap(), "1");
+        assert_eq!(service.env.get("OLLAMA_HOST").unwrap(), "127.0.0.1:11434");
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn env_exports_are_sorted_and_quoted() {
+        let _project = TestProject::new();
+        let mut cfg = config::Config::default();
+        cfg.ollama_server.extra.clear();
+        cfg.ollama_server
+            .extra
+            .insert("models".into(), toml::Value::String("/data/\"my\" $models".into()));
+
+        let service = create_ollama_service(&cfg.ollama_server);
+        assert_eq!(
+            service.env_exports(),
+            [
+                r#"export OLLAMA_HOST="127.0.0.1:11434""#,
+                r#"export OLLAMA_MODELS="/data/\"my\" \$models""#,
+            ]
+        );
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn state_files_include_non_default_ports() {
+        let _project = TestProject::new();
+        let mut cfg = config::Config::default();
+        let default = create_ollama_service(&cfg.ollama_server);
+        assert_eq!(default.log_path().unwrap().file_name().unwrap(), "ollama.log");
+        assert_eq!(default.pid_path().unwrap().file_name().unwrap(), "ollama.pid");
+        assert_eq!(default.config_path().unwrap().file_name().unwrap(), "ollama.config");
+
+        cfg.ollama_server.port = 11500;
+        let first = create_ollama_service(&cfg.ollama_server);
+        cfg.ollama_server.port = 11501;
+        let second = create_ollama_service(&cfg.ollama_server);
+        assert_eq!(first.log_path().unwrap().file_name().unwrap(), "ollama-11500.log");
+        assert_ne!(first.log_path().unwrap(), second.log_path().unwrap());
+        assert_ne!(first.pid_path().unwrap(), second.pid_path().unwrap());
+        assert_ne!(first.config_path().unwrap(), second.config_path().unwrap());
+
+        cfg.mlx_server.port = 5050;
+        assert_eq!(create_mlx_service(&cfg.mlx_server).pid_filename, "mlx-5050.pid");
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn base_url_formats_host_and_port() {
+        let _project = TestProject::new();
+        let mut cfg = config::Config::default();
+        let ollama = create_ollama_service(&cfg.ollama_server);
+        assert_eq!(ollama.base_url(), "http://127.0.0.1:11434");
+        assert_eq!(ollama.url("/api/tags"), "http://127.0.0.1:11434/api/tags");
+
+        cfg.mlx_server.host = "::1".into();
+        assert_eq!(create_mlx_service(&cfg.mlx_server).base_url(), "http://[::1]:8080");
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn https_scheme_is_used_in_urls() {
+        let _project = TestProject::new();
+        let mut cfg = config::Config::default();
+        cfg.ollama_server.scheme = "https".into();
+        assert_eq!(
+            create_ollama_service(&cfg.ollama_server).url("/api/tags"),
+            "https://127.0.0.1:11434/api/tags"
+        );
+        assert!(cfg.validate().is_ok());
+
+        cfg.mlx_server.scheme = "ftp".into();
+        let err = cfg.validate().expect_err("unsupported scheme should be rejected");
+        assert!(err.to_string().contains("mlx_server.scheme"), "error: {err}");
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn overrides_extend_command_env_and_state_files() {
+        let _project = TestProject::new();
+        let cfg = config::Config::default();
+        let overrides = ServiceOverrides {
+            extra_args: vec!["--log-level".into(), "debug".into()],
+            env: vec![("MLX_CACHE".into(), "/tmp/cache".into())],
+            state_stem: Some("mlx-ci".into()),
+        };
+        let service = create_mlx_service(&cfg.mlx_server).with_overrides(&overrides);
+        assert_eq!(service.command[0], "mlx_lm.server");
+        assert!(service.command.ends_with(&["--log-level".to_string(), "debug".to_string()]));
+        assert_eq!(service.env["MLX_CACHE"], "/tmp/cache");
+        assert_eq!(service.log_path().unwrap().file_name().unwrap(), "mlx-ci.log");
+        assert_eq!(service.stderr_log_path().unwrap().file_name().unwrap(), "mlx-ci.err.log");
+        assert_eq!(service.pid_path().unwrap().file_name().unwrap(), "mlx-ci.pid");
+
+        let untouched = create_ollama_service(&cfg.ollama_server);
+        let same = untouched.clone().with_overrides(&ServiceOverrides::default());
+        assert_eq!(same.command, untouched.command);
+        assert_eq!(same.log_filename, untouched.log_filename);
+    }
+
+    #[test]
+    fn shell_quote_wraps_special_characters() {
+        assert_eq!(shell_quote("llama3.2:3b"), "llama3.2:3b");
+        assert_eq!(shell_quote("two words"), "'two words'");
+        assert_eq!(shell_quote("it's"), r"'it'\''s'");
+        assert_eq!(shell_quote(""), "''");
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn ipv6_hosts_are_not_double_bracketed() {
+        let _project = TestProject::new();
+        let mut cfg = config::Config::default();
+        cfg.mlx_server.host = "[::1]".into();
+        cfg.ollama_server.host = "::1".into();
+
+        let ollama = create_ollama_service(&cfg.ollama_server);
+        assert_eq!(ollama.host, "::1");
+        assert_eq!(ollama.env.get("OLLAMA_HOST").unwrap(), "[::1]:11434");
+
+        let created = create_mlx_service(&cfg.mlx_server);
+        process::write_config(&created).expect("config write should succeed");
+        let loaded = load_mlx_service(&cfg.mlx_server).expect("mlx service should load");
+        assert_eq!(loaded.host, "::1");
+        let host_index = loaded.command.iter().position(|arg| arg == "--host").unwrap();
+        assert_eq!(loaded.command[host_index + 1], "::1");
+        assert_eq!(config::format_host_port(&loaded.host, loaded.port), "[::1]:8080");
+
+        process::remove_config(&created).expect("config removal should succeed");
+    }
 }
@@ -13,16 +13,33 @@ pub enum AppError {
         service: String,
         message: String,
     },
+    /// A diagnostic such as `fusion doctor` ran fine but found problems.
+    ChecksFailed(String),
+    /// HTTP exchange with a managed service failed (connection, status, or response body).
+    Http {
+        service: String,
+        message: String,
+        /// Underlying transport error, kept so callers can tell timeouts from DNS failures.
+        source: Option<reqwest::Error>,
+    },
 }
 
 impl Display for AppError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             AppError::Io(err) => write!(f, "{}", err),
-            AppError::ConfigError(message) => write!(f, "{message}"),
+            AppError::ConfigError(message) | AppError::ChecksFailed(message) => {
+                write!(f, "{message}")
+            }
             AppError::ProcessError { service, message } => {
                 write!(f, "Service '{service}' error: {message}")
             }
+            AppError::Http { service, message, source: Some(source) } => {
+                write!(f, "Service '{service}' request failed: {message}: {source}")
+            }
+            AppError::Http { service, message, source: None } => {
+                write!(f, "Service '{service}' request failed: {message}")
+            }
         }
     }
 }
@@ -31,7 +48,11 @@ impl Error for AppError {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
         match self {
             AppError::Io(err) => Some(err),
-            AppError::ConfigError(_) | AppError::ProcessError { .. } => None,
+            AppError::Http { source: Some(source), .. } => Some(source),
+            AppError::ConfigError(_)
+            | AppError::ChecksFailed(_)
+            | AppError::ProcessError { .. }
+            | AppError::Http { source: None, .. } => None,
         }
     }
 }
@@ -47,16 +68,77 @@ impl AppError {
         AppError::ConfigError(message.into())
     }
 
+    pub(crate) fn checks_failed<S: Into<String>>(message: S) -> Self {
+        AppError::ChecksFailed(message.into())
+    }
+
     pub(crate) fn process_error<S: Into<String>, M: Into<String>>(service: S, message: M) -> Self {
         AppError::ProcessError { service: service.into(), message: message.into() }
     }
 
+    /// HTTP failure without a transport error, such as an unexpected status or response shape.
+    pub(crate) fn http_error<S: Into<String>, M: Into<String>>(service: S, message: M) -> Self {
+        AppError::Http { service: service.into(), message: message.into(), source: None }
+    }
+
+    /// HTTP failure caused by `source`, which stays reachable through `Error::source`.
+    pub(crate) fn http_source<S: Into<String>, M: Into<String>>(
+        service: S,
+        message: M,
+        source: reqwest::Error,
+    ) -> Self {
+        AppError::Http { service: service.into(), message: message.into(), source: Some(source) }
+    }
+
+    /// Process exit status for this error class: failed checks 1, config 2, process 3, HTTP 4,
+    /// I/O 5.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            AppError::ChecksFailed(_) => 1,
+            AppError::ConfigError(_) => 2,
+            AppError::ProcessError { .. } => 3,
+            AppError::Http { .. } => 4,
+            AppError::Io(_) => 5,
+        }
+    }
+
     /// Provide an `io::ErrorKind`-like view for callers expecting legacy behavior.
     pub fn kind(&self) -> io::ErrorKind {
         match self {
             AppError::Io(err) => err.kind(),
             AppError::ConfigError(_) => io::ErrorKind::InvalidInput,
-            AppError::ProcessError { .. } => io::ErrorKind::Other,
+            AppError::ChecksFailed(_) | AppError::ProcessError { .. } => io::ErrorKind::Other,
+            AppError::Http { .. } => io::ErrorKind::ConnectionRefused,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exit_codes_distinguish_error_classes() {
+        assert_eq!(AppError::checks_failed("1 check failed").exit_code(), 1);
+        assert_eq!(AppError::config_error("bad key").exit_code(), 2);
+        assert_eq!(AppError::process_error("mlx", "died").exit_code(), 3);
+        assert_eq!(AppError::http_error("mlx", "Connection failed").exit_code(), 4);
+        assert_eq!(AppError::from(io::Error::other("disk")).exit_code(), 5);
+    }
+
+    #[test]
+    fn http_errors_preserve_their_source() {
+        let port = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+        let err = reqwest::blocking::get(format!("http://127.0.0.1:{port}/")).unwrap_err();
+        let app_error = AppError::http_source("ollama", "Connection failed", err);
+
+        let source = app_error.source().expect("source should be kept");
+        assert!(source.downcast_ref::<reqwest::Error>().is_some_and(reqwest::Error::is_connect));
+        assert!(
+            app_error
+                .to_string()
+                .starts_with("Service 'ollama' request failed: Connection failed: ")
+        );
+        assert!(AppError::http_error("ollama", "status 500").source().is_none());
+    }
+}
@@ -1,12 +1,41 @@
-use clap::{Parser, Subcommand};
-use fusion::cli::{self, ServiceConfigCommand, ServiceType};
+use clap::{Args, Parser, Subcommand};
+use fusion::cli::{
+    self, ConfigFormat, DownOptions, LogOptions, ServiceConfigCommand, ServiceType, StatusFilter,
+    UpOptions,
+};
+use fusion::core::config::{self, ValueKind};
+use fusion::core::{http, http_log, log_stamp, paths};
 use fusion::error::AppError;
+use std::io::IsTerminal;
+use std::path::PathBuf;
+use std::time::Duration;
 
 #[derive(Parser)]
 #[command(name = "fusion")]
 #[command(version)]
 #[command(about = "Fusion CLI for managing local LLM runtimes", long_about = None)]
 struct Cli {
+    /// Log diagnostics to stderr (-v info, -vv debug, -vvv trace)
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    verbose: u8,
+    /// Use this config file instead of the default location
+    #[arg(long, global = true, value_name = "PATH")]
+    config: Option<PathBuf>,
+    /// Append raw HTTP requests and responses to requests.log (also FUSION_HTTP_DEBUG=1)
+    #[arg(long, global = true, default_value_t = false)]
+    debug_http: bool,
+    /// Seconds to wait for HTTP connections (also FUSION_CONNECT_TIMEOUT_SECS; default 10)
+    #[arg(long, global = true, value_name = "SECS")]
+    timeout_connect: Option<u64>,
+    /// Retries for failed HTTP connections (also FUSION_HTTP_RETRIES; default 2)
+    #[arg(long, global = true, value_name = "N")]
+    retries: Option<u32>,
+    /// Treat config safety warnings (loose file permissions, public bind hosts) as errors
+    #[arg(long, global = true, default_value_t = false)]
+    strict: bool,
+    /// Fail `up` when several config keys set the same server environment variable
+    #[arg(long, global = true, default_value_t = false)]
+    strict_env: bool,
     #[command(subcommand)]
     command: Commands,
 }
@@ -23,61 +52,289 @@ enum Commands {
     Mlx(ServiceCommands),
     /// Display runtime status information for all services
     #[clap(visible_alias = "p")]
-    Ps,
+    Ps {
+        /// Keep refreshing the status until interrupted
+        #[arg(short, long, default_value_t = false)]
+        watch: bool,
+        /// Seconds between refreshes in watch mode
+        #[arg(long, default_value_t = 2, requires = "watch")]
+        interval: u64,
+        /// Only list services in this state: running or stopped
+        #[arg(long, value_name = "STATE")]
+        filter: Option<StatusFilter>,
+        /// Number of services to query at the same time
+        #[arg(long, value_name = "N", default_value_t = 1, value_parser = clap::value_parser!(u16).range(1..))]
+        count: u16,
+        /// Print tab-separated `name state pid host port` lines for scripts
+        #[arg(long, default_value_t = false, conflicts_with = "watch")]
+        porcelain: bool,
+    },
+    /// Summarise which services are up
+    Status {
+        /// Print a single `ollama:up mlx:down` line for prompts and status bars
+        #[arg(long, default_value_t = false)]
+        short: bool,
+    },
+    /// Print the log tail of every service in one go
+    Logs {
+        #[command(flatten)]
+        args: LogArgs,
+    },
+    /// Print a Prometheus text-format metrics snapshot for all services
+    Metrics,
+    /// Run an OpenAI-compatible proxy that routes requests to the backend serving the model
+    Proxy {
+        /// Address to bind the proxy listener on
+        #[arg(long, default_value = "127.0.0.1")]
+        host: String,
+        /// Port to bind the proxy listener on
+        #[arg(short, long, default_value_t = 9000)]
+        port: u16,
+    },
+    /// Diagnose common setup problems (missing binaries, unwritable state dir, ports)
+    Doctor,
+    /// Show version, build commit, rustc, and config path
+    Version {
+        /// Print the details as JSON for bug reports
+        #[arg(long, default_value_t = false)]
+        json: bool,
+    },
     /// Manage global configuration
     #[clap(visible_alias = "cf")]
     #[command(subcommand)]
     Config(ConfigCommands),
+    /// Copy stdin into a log file, prefixing each line with a UTC timestamp (used by `up`)
+    #[command(name = log_stamp::STAMP_SUBCOMMAND, hide = true)]
+    StampLog { path: PathBuf },
+}
+
+/// Line selection shared by `log` and `logs`.
+#[derive(Args)]
+struct LogArgs {
+    /// Only show lines logged within this window, e.g. 90s, 10m, 2h, 1d
+    #[arg(long, value_name = "DURATION", value_parser = log_stamp::parse_since)]
+    since: Option<Duration>,
+    /// Number of lines to show
+    #[arg(short = 'n', long, value_name = "N", default_value_t = 15, conflicts_with = "since")]
+    lines: usize,
+    /// Show the first lines instead of the last
+    #[arg(long, default_value_t = false, conflicts_with = "since")]
+    head: bool,
+    /// Only show lines containing this text
+    #[arg(long, value_name = "TEXT")]
+    grep: Option<String>,
+}
+
+impl From<LogArgs> for LogOptions {
+    fn from(args: LogArgs) -> Self {
+        LogOptions { since: args.since, lines: args.lines, head: args.head, grep: args.grep }
+    }
 }
 
 #[derive(Subcommand)]
 enum ServiceCommands {
     /// Start the service using configuration defaults
-    Up,
+    Up {
+        /// Print the resolved command and environment without starting anything
+        #[arg(long, default_value_t = false)]
+        dry_run: bool,
+        /// Bind host for this launch, overriding the configuration
+        #[arg(long)]
+        host: Option<String>,
+        /// Bind port for this launch, overriding the configuration
+        #[arg(short, long)]
+        port: Option<u16>,
+        /// Extra environment variable for the server process (repeatable)
+        #[arg(long = "env", value_name = "KEY=VALUE", value_parser = config::parse_env_assignment)]
+        env: Vec<(String, String)>,
+        /// Seconds to wait for readiness (overrides FUSION_STARTUP_TIMEOUT_SECS; 0 = don't wait)
+        #[arg(long, value_name = "SECS", conflicts_with = "no_wait")]
+        timeout: Option<u64>,
+        /// Spawn and print the PID without waiting for readiness
+        #[arg(long, default_value_t = false)]
+        no_wait: bool,
+        /// Print the outcome as a JSON object instead of progress lines
+        #[arg(long, default_value_t = false, conflicts_with = "dry_run")]
+        json: bool,
+        /// Acknowledge that a 0.0.0.0 or :: host exposes the server to the network
+        #[arg(long, default_value_t = false)]
+        allow_public_bind: bool,
+        /// Consider the server ready once its port accepts connections, skipping inference
+        #[arg(long, default_value_t = false, conflicts_with = "no_wait")]
+        wait_for_port: bool,
+        /// Echo the server log while waiting for readiness
+        #[arg(long, default_value_t = false, conflicts_with_all = ["no_wait", "json"])]
+        attach: bool,
+    },
     /// Stop the service
     #[clap(visible_alias = "d")]
     Down {
         /// Force-stop services using SIGKILL
         #[arg(short, long, default_value_t = false)]
         force: bool,
+        /// Print the outcome as a JSON object instead of text
+        #[arg(long, default_value_t = false)]
+        json: bool,
+        /// With --force, kill processes matched by command line without asking
+        #[arg(short, long, default_value_t = false, requires = "force")]
+        yes: bool,
     },
     /// Display runtime status for this service
     Ps,
     /// Show log file locations for this service
     #[clap(visible_alias = "lg")]
-    Log,
+    Log {
+        #[command(flatten)]
+        args: LogArgs,
+    },
+    /// Download a model through the running server (Ollama only)
+    Pull {
+        /// Model name, e.g. llama3.2:3b
+        model: String,
+    },
+    /// Print the server environment as shell `export` lines
+    Env,
+    /// Check that the server answers HTTP, reporting latency without running inference
+    Ping,
     /// Check health by running a minimal inference request
     #[clap(visible_alias = "hl")]
-    Health,
+    Health {
+        /// Print one JSON object with the result, latency, and response
+        #[arg(long, default_value_t = false)]
+        json: bool,
+    },
+    /// List models available from the running backend
+    #[clap(visible_alias = "ls")]
+    Models,
+    /// Show a model's family, size, quantization, and parameters (Ollama only)
+    Show {
+        /// Model name; defaults to the configured model
+        model: Option<String>,
+    },
+    /// Send a raw request to the backend API and print the response, e.g. `api GET /api/ps`
+    Api {
+        /// HTTP method: GET, POST, PUT, PATCH, DELETE, or HEAD
+        method: String,
+        /// Request path starting with `/`
+        path: String,
+        /// JSON request body
+        #[arg(long, value_name = "JSON")]
+        body: Option<String>,
+    },
+    /// Print a systemd user unit for this service, or install it with --install
+    Systemd {
+        /// Write the unit to the systemd user directory instead of printing it
+        #[arg(long, default_value_t = false)]
+        install: bool,
+    },
+    /// Print a launchd agent plist for this service, or install it with --install
+    Launchd {
+        /// Write the plist to ~/Library/LaunchAgents instead of printing it
+        #[arg(long, default_value_t = false)]
+        install: bool,
+        /// Run `launchctl load` after installing the plist
+        #[arg(long, default_value_t = false, requires = "install")]
+        load: bool,
+    },
 }
 
 #[derive(Subcommand)]
 enum ConfigCommands {
     /// Show the current configuration file contents
-    Show,
+    Show {
+        /// toml prints the file as written; json prints the typed config with defaults
+        #[arg(long, value_name = "FORMAT", default_value = "toml")]
+        format: ConfigFormat,
+    },
     /// Create a symlink to the configuration file in the current directory
     Edit,
     /// Print the configuration file path
-    Path,
+    Path {
+        /// Create the file with default values first if it does not exist
+        #[arg(long, default_value_t = false)]
+        ensure: bool,
+        /// Print the directory containing the file instead
+        #[arg(long, default_value_t = false)]
+        dir: bool,
+    },
     /// Reset configuration file to default values
     Reset,
+    /// Show settings that differ from the defaults
+    Diff,
+    /// List every configuration key with its current value and type
+    ListKeys,
+    /// Replace the config with a validated TOML file
+    Import {
+        /// TOML file to import
+        path: PathBuf,
+        /// Overwrite an existing config that differs from the file
+        #[arg(long, default_value_t = false)]
+        force: bool,
+    },
+    /// Copy the current config to a file, or to stdout with `-`
+    Export {
+        /// Destination file, or `-` for stdout
+        path: PathBuf,
+    },
+    /// Upgrade the config file to the current schema, keeping existing values
+    Migrate,
+    /// Print a JSON Schema describing config.toml, for editor completion
+    Schema,
+    /// Set a configuration value by dotted key (e.g. ollama_server.port 11500)
+    Set {
+        /// Dotted key path, see `fusion config list-keys`
+        key: String,
+        /// New value; booleans and numbers are inferred unless a type flag is given
+        value: String,
+        /// Store the value as a string
+        #[arg(long, group = "value_type")]
+        string: bool,
+        /// Store the value as an integer
+        #[arg(long, group = "value_type")]
+        int: bool,
+        /// Store the value as a float
+        #[arg(long, group = "value_type")]
+        float: bool,
+        /// Store the value as a boolean
+        #[arg(long, group = "value_type")]
+        bool: bool,
+    },
 }
 
 fn main() {
     let cli = Cli::parse();
+    init_tracing(cli.verbose);
+    paths::set_config_file_override(cli.config);
+    config::set_strict(cli.strict);
+    config::set_strict_env(cli.strict_env);
+    http_log::set_enabled(cli.debug_http);
+    http::set_overrides(cli.timeout_connect, cli.retries);
 
     let result: Result<(), AppError> = match cli.command {
         Commands::Ollama(service_command) => {
             handle_service_command(ServiceType::Ollama, service_command)
         }
         Commands::Mlx(service_command) => handle_service_command(ServiceType::Mlx, service_command),
-        Commands::Ps => cli::handle_ps(),
+        Commands::Ps { watch: true, interval, filter, count, .. } => {
+            cli::handle_ps_watch(interval, filter, count.into())
+        }
+        Commands::Ps { porcelain: true, filter, count, .. } => {
+            cli::handle_ps_porcelain(filter, count.into())
+        }
+        Commands::Ps { filter, count, .. } => cli::handle_ps_filtered(filter, count.into()),
+        Commands::Status { short } => cli::handle_status(short),
+        Commands::Logs { args } => cli::handle_logs_all(&args.into()),
+        Commands::Metrics => cli::handle_metrics(),
+        Commands::Doctor => cli::handle_doctor(),
+        Commands::Version { json } => cli::handle_version(json),
+        Commands::Proxy { host, port } => cli::handle_proxy(&host, port),
         Commands::Config(config_command) => cli::handle_config(map_config_command(config_command)),
+        Commands::StampLog { path } => log_stamp::relay_to_file(std::io::stdin().lock(), &path),
     };
 
     if let Err(err) = result {
         eprintln!("Error: {err}");
-        std::process::exit(1);
+        std::process::exit(err.exit_code());
     }
 }
 
@@ -86,19 +343,96 @@ fn handle_service_command(
     command: ServiceCommands,
 ) -> Result<(), AppError> {
     match command {
-        ServiceCommands::Up => cli::handle_up(service_type),
-        ServiceCommands::Down { force } => cli::handle_down(service_type, force),
+        ServiceCommands::Up {
+            dry_run,
+            host,
+            port,
+            env,
+            timeout,
+            no_wait,
+            json,
+            allow_public_bind,
+            wait_for_port,
+            attach,
+        } => cli::handle_up_with_options(
+            service_type,
+            &UpOptions {
+                dry_run,
+                host,
+                port,
+                env,
+                timeout_secs: timeout,
+                no_wait,
+                json,
+                allow_public_bind,
+                wait_for_port,
+                attach,
+            },
+        ),
+        ServiceCommands::Down { force, json, yes } => {
+            cli::handle_down_with_options(service_type, &DownOptions { force, json, yes })
+        }
         ServiceCommands::Ps => cli::handle_ps_single(service_type),
-        ServiceCommands::Log => cli::handle_logs_single(service_type),
-        ServiceCommands::Health => cli::handle_health_single(service_type),
+        ServiceCommands::Log { args } => cli::handle_logs_single(service_type, &args.into()),
+        ServiceCommands::Pull { model } => cli::handle_pull(service_type, &model),
+        ServiceCommands::Env => cli::handle_env(service_type),
+        ServiceCommands::Ping => cli::handle_ping(service_type),
+        ServiceCommands::Health { json } => cli::handle_health_single(service_type, json),
+        ServiceCommands::Models => cli::handle_models(service_type),
+        ServiceCommands::Show { model } => cli::handle_show(service_type, model.as_deref()),
+        ServiceCommands::Api { method, path, body } => {
+            cli::handle_api(service_type, &method, &path, body.as_deref())
+        }
+        ServiceCommands::Systemd { install } => cli::handle_systemd(service_type, install),
+        ServiceCommands::Launchd { install, load } => {
+            cli::handle_launchd(service_type, install, load)
+        }
     }
 }
 
+/// Install a stderr subscriber only when `-v` is given so default output stays unchanged.
+/// `RUST_LOG` takes precedence over the verbosity count when set.
+fn init_tracing(verbose: u8) {
+    let level = match verbose {
+        0 => return,
+        1 => "info",
+        2 => "debug",
+        _ => "trace",
+    };
+    let filter = tracing_subscriber::EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new(format!("fusion={level}")));
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr)
+        .with_ansi(std::io::stderr().is_terminal())
+        .init();
+}
+
 fn map_config_command(cmd: ConfigCommands) -> ServiceConfigCommand {
     match cmd {
-        ConfigCommands::Show => ServiceConfigCommand::Show,
+        ConfigCommands::Show { format } => ServiceConfigCommand::Show { format },
         ConfigCommands::Edit => ServiceConfigCommand::Edit,
-        ConfigCommands::Path => ServiceConfigCommand::Path,
+        ConfigCommands::Path { ensure, dir } => ServiceConfigCommand::Path { ensure, dir },
         ConfigCommands::Reset => ServiceConfigCommand::Reset,
+        ConfigCommands::Diff => ServiceConfigCommand::Diff,
+        ConfigCommands::ListKeys => ServiceConfigCommand::ListKeys,
+        ConfigCommands::Import { path, force } => ServiceConfigCommand::Import { path, force },
+        ConfigCommands::Export { path } => ServiceConfigCommand::Export { path },
+        ConfigCommands::Migrate => ServiceConfigCommand::Migrate,
+        ConfigCommands::Schema => ServiceConfigCommand::Schema,
+        ConfigCommands::Set { key, value, string, int, float, bool } => {
+            let kind = if string {
+                Some(ValueKind::String)
+            } else if int {
+                Some(ValueKind::Integer)
+            } else if float {
+                Some(ValueKind::Float)
+            } else if bool {
+                Some(ValueKind::Boolean)
+            } else {
+                None
+            };
+            ServiceConfigCommand::Set { key, value, kind }
+        }
     }
 }
@@ -1,6 +1,9 @@
 use std::env;
 use std::ffi::OsString;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
 use std::path::PathBuf;
+use std::thread::{self, JoinHandle};
 use tempfile::TempDir;
 
 /// Integration test harness configuring an isolated Fusion workspace.
@@ -70,3 +73,44 @@ impl Drop for CliTestContext {
         }
     }
 }
+
+/// Serve a single HTTP response on an ephemeral port, returning the port and a handle that
+/// yields the request line (e.g. `GET /v1/models HTTP/1.1`) once the exchange completes.
+#[allow(dead_code)]
+pub fn serve_once(status: &'static str, body: &'static str) -> (u16, JoinHandle<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("stub listener should bind");
+    let port = listener.local_addr().unwrap().port();
+
+    let handle = thread::spawn(move || {
+        let (stream, _) = listener.accept().expect("accept should succeed");
+        let mut reader = BufReader::new(stream);
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line).expect("read request line");
+
+        let mut content_length = 0usize;
+        loop {
+            let mut header = String::new();
+            reader.read_line(&mut header).expect("read header");
+            if header.trim().is_empty() {
+                break;
+            }
+            if let Some((name, value)) = header.split_once(':')
+                && name.trim().eq_ignore_ascii_case("content-length")
+            {
+                content_length = value.trim().parse().expect("parse content length");
+            }
+        }
+        let mut request_body = vec![0u8; content_length];
+        reader.read_exact(&mut request_body).expect("read body");
+
+        let response = format!(
+            "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{body}",
+            body.len()
+        );
+        reader.get_mut().write_all(response.as_bytes()).expect("write response");
+        reader.get_mut().flush().ok();
+        request_line.trim().to_string()
+    });
+
+    (port, handle)
+}
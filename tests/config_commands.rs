@@ -1,5 +1,10 @@
-use fusion::cli::{self, ServiceConfigCommand};
+mod common;
+
+use assert_cmd::Command;
+use common::CliTestContext;
+use fusion::cli::{self, ConfigFormat, ServiceConfigCommand};
 use fusion::core::config::load_config;
+use predicates::prelude::*;
 
 #[test]
 #[serial_test::serial]
@@ -7,7 +12,8 @@ fn llm_config_show_works() {
     // Ensure the config file exists before running the command.
     let _ = load_config().expect("load_config should succeed");
 
-    cli::handle_config(ServiceConfigCommand::Show).expect("config show should succeed");
+    cli::handle_config(ServiceConfigCommand::Show { format: ConfigFormat::Toml })
+        .expect("config show should succeed");
 }
 
 #[test]
@@ -32,3 +38,142 @@ fn llm_config_reset_restores_defaults() {
     assert_eq!(reset.ollama_server.port, 11434); // default port
     assert_eq!(reset.mlx_server.model, "mlx-community/Llama-3.2-3B-Instruct-4bit"); // default model
 }
+
+#[test]
+#[serial_test::serial]
+fn config_flag_overrides_config_file_location() {
+    let ctx = CliTestContext::new();
+    let custom = ctx.root.path().join("elsewhere/custom.toml");
+
+    Command::cargo_bin("fusion")
+        .unwrap()
+        .args(["--config", custom.to_str().unwrap(), "config", "path"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(format!("{}\n", custom.display())));
+
+    Command::cargo_bin("fusion")
+        .unwrap()
+        .args(["config", "set", "ollama_server.port", "11500", "--config"])
+        .arg(&custom)
+        .assert()
+        .success();
+    let contents = std::fs::read_to_string(&custom).expect("custom config should be written");
+    assert!(contents.contains("port = 11500"), "custom config: {contents}");
+    assert!(!ctx.pid_dir().join("config.toml").exists(), "default config should be untouched");
+}
+
+#[test]
+#[serial_test::serial]
+fn config_path_ensure_creates_the_file_on_a_fresh_config_dir() {
+    let ctx = CliTestContext::new();
+    let config_dir = ctx.root.path().join("fresh");
+    let config_file = config_dir.join("config.toml");
+
+    Command::cargo_bin("fusion")
+        .unwrap()
+        .env("FUSION_CONFIG_DIR", &config_dir)
+        .args(["config", "path"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(format!("{}\n", config_file.display())));
+    assert!(!config_file.exists(), "plain `config path` should not create the file");
+
+    Command::cargo_bin("fusion")
+        .unwrap()
+        .env("FUSION_CONFIG_DIR", &config_dir)
+        .args(["config", "path", "--ensure"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(format!("{}\n", config_file.display())));
+    let contents = std::fs::read_to_string(&config_file).expect("--ensure should create the file");
+    assert!(contents.contains("[ollama_server]"), "config: {contents}");
+
+    Command::cargo_bin("fusion")
+        .unwrap()
+        .env("FUSION_CONFIG_DIR", &config_dir)
+        .args(["config", "path", "--dir"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(format!("{}\n", config_dir.display())));
+}
+
+#[test]
+#[serial_test::serial]
+fn config_export_and_import_round_trip() {
+    let ctx = CliTestContext::new();
+    let mut cfg = load_config().expect("load_config should succeed");
+    cfg.mlx_server.port = 8181;
+    fusion::core::config::save_config(&cfg).expect("save_config should succeed");
+
+    let exported = ctx.root.path().join("exported.toml");
+    cli::handle_config(ServiceConfigCommand::Export { path: exported.clone() })
+        .expect("export should succeed");
+
+    cli::handle_config(ServiceConfigCommand::Reset).expect("reset should succeed");
+    let import = ServiceConfigCommand::Import { path: exported.clone(), force: false };
+    assert!(cli::handle_config(import).is_err(), "differing config needs --force");
+
+    cli::handle_config(ServiceConfigCommand::Import { path: exported, force: true })
+        .expect("forced import should succeed");
+    assert_eq!(load_config().unwrap().mlx_server.port, 8181);
+}
+
+#[test]
+#[serial_test::serial]
+fn config_import_rejects_invalid_files() {
+    let ctx = CliTestContext::new();
+    let original = load_config().expect("load_config should succeed");
+    let invalid = ctx.root.path().join("invalid.toml");
+
+    std::fs::write(&invalid, "[ollama_server]\nport = \"not-a-port\"\n").unwrap();
+    let import = ServiceConfigCommand::Import { path: invalid.clone(), force: true };
+    assert!(cli::handle_config(import).is_err(), "type errors should be rejected");
+
+    std::fs::write(&invalid, "[mlx_server]\nport = 0\n").unwrap();
+    let err = cli::handle_config(ServiceConfigCommand::Import { path: invalid, force: true })
+        .expect_err("port 0 should fail validation");
+    assert!(err.to_string().contains("mlx_server.port"), "error: {err}");
+
+    assert_eq!(load_config().unwrap().mlx_server.port, original.mlx_server.port);
+}
+
+#[test]
+#[serial_test::serial]
+fn config_show_json_renders_the_typed_config() {
+    let ctx = CliTestContext::new();
+    let config_path = ctx.root.path().join("config.toml");
+
+    let output = Command::cargo_bin("fusion")
+        .unwrap()
+        .args(["--config", config_path.to_str().unwrap(), "config", "show", "--format", "json"])
+        .output()
+        .expect("config show should run");
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("config show --format json prints JSON");
+    assert_eq!(json["ollama_server"]["port"], 11434);
+}
+
+#[test]
+#[serial_test::serial]
+fn config_schema_is_json_describing_the_server_sections() {
+    let _ctx = CliTestContext::new();
+
+    let output = Command::cargo_bin("fusion")
+        .unwrap()
+        .args(["config", "schema"])
+        .output()
+        .expect("config schema should run");
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    let schema: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("config schema prints JSON");
+    let ollama = &schema["properties"]["ollama_server"];
+    assert_eq!(ollama["type"], "object");
+    assert_eq!(ollama["properties"]["port"]["type"], "integer");
+    assert_eq!(ollama["properties"]["port"]["default"], 11434);
+    assert_eq!(ollama["properties"]["ready_path"]["type"], "string");
+    assert!(ollama["additionalProperties"].is_object());
+}
@@ -1,6 +1,7 @@
 mod common;
 
-use common::CliTestContext;
+use assert_cmd::Command;
+use common::{CliTestContext, serve_once};
 use fusion::cli::{self, ServiceType};
 use fusion::core::config::{load_config, save_config};
 use serial_test::serial;
@@ -70,7 +71,7 @@ fn test_health_inference_request(service_type: ServiceType, expected_model: &str
     }
     save_config(&cfg).expect("save_config should succeed");
 
-    cli::handle_health_single(service_type).expect("health should succeed");
+    cli::handle_health_single(service_type, false).expect("health should succeed");
 
     stub_thread.join().expect("stub thread should join");
 }
@@ -124,8 +125,56 @@ fn llm_health_returns_error_on_failure() {
     cfg.ollama_server.port = port;
     save_config(&cfg).expect("save_config should succeed");
 
-    let result = cli::handle_health_single(ServiceType::Ollama);
+    let result = cli::handle_health_single(ServiceType::Ollama, false);
     assert!(result.is_err(), "health should fail on HTTP error");
 
     stub_thread.join().expect("stub thread should join");
 }
+
+#[test]
+#[serial]
+fn llm_health_errors_when_completion_has_no_choices() {
+    let _ctx = CliTestContext::new();
+    let (port, stub) = serve_once("200 OK", r#"{"id":"x","choices":[]}"#);
+    let mut cfg = load_config().expect("load_config should succeed");
+    cfg.mlx_server.port = port;
+    save_config(&cfg).expect("save_config should succeed");
+
+    let err =
+        cli::handle_health_single(ServiceType::Mlx, false).expect_err("empty choices should fail");
+    let message = err.to_string();
+    assert!(message.contains("no completion content"), "error: {message}");
+    assert!(message.contains(r#""choices":[]"#), "error: {message}");
+
+    stub.join().expect("stub thread should join");
+}
+
+#[test]
+#[serial]
+fn health_json_reports_result_latency_and_model() {
+    let _ctx = CliTestContext::new();
+    let (port, stub) = serve_once(
+        "200 OK",
+        r#"{"choices":[{"message":{"role":"assistant","content":"I am a mock LLM."}}]}"#,
+    );
+    let mut cfg = load_config().expect("load_config should succeed");
+    cfg.ollama_server.port = port;
+    save_config(&cfg).expect("save_config should succeed");
+
+    let output = Command::cargo_bin("fusion")
+        .unwrap()
+        .args(["ollama", "health", "--json"])
+        .output()
+        .expect("health should run");
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    let report: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("health --json prints one JSON object");
+    assert_eq!(report["service"], "ollama");
+    assert_eq!(report["healthy"], true);
+    assert_eq!(report["model"], "llama3.2:3b");
+    assert_eq!(report["response"], "I am a mock LLM.");
+    assert!(report["latency_ms"].is_u64());
+
+    stub.join().expect("stub thread should join");
+}
@@ -0,0 +1,140 @@
+mod common;
+
+use assert_cmd::Command;
+use common::{CliTestContext, serve_once};
+use fusion::core::config::{load_config, save_config};
+use predicates::prelude::*;
+use serial_test::serial;
+
+#[test]
+#[serial]
+fn mlx_models_prints_openai_model_ids() {
+    let _ctx = CliTestContext::new();
+    let (port, handle) = serve_once(
+        "200 OK",
+        r#"{"object":"list","data":[{"id":"mlx-community/Llama-3.2-3B-Instruct-4bit"},{"id":"mlx-community/Qwen2.5-7B"}]}"#,
+    );
+    let mut cfg = load_config().expect("load_config should succeed");
+    cfg.mlx_server.port = port;
+    save_config(&cfg).expect("save_config should succeed");
+
+    Command::cargo_bin("fusion")
+        .unwrap()
+        .args(["mlx", "models"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("• mlx-community/Llama-3.2-3B-Instruct-4bit"))
+        .stdout(predicate::str::contains("• mlx-community/Qwen2.5-7B"));
+
+    assert_eq!(handle.join().expect("stub thread should join"), "GET /v1/models HTTP/1.1");
+}
+
+#[test]
+#[serial]
+fn ollama_models_prints_tags_with_sizes() {
+    let _ctx = CliTestContext::new();
+    let (port, handle) =
+        serve_once("200 OK", r#"{"models":[{"name":"llama3.2:3b","size":2019393189}]}"#);
+    let mut cfg = load_config().expect("load_config should succeed");
+    cfg.ollama_server.port = port;
+    save_config(&cfg).expect("save_config should succeed");
+
+    Command::cargo_bin("fusion")
+        .unwrap()
+        .args(["ollama", "models"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("• llama3.2:3b (2.0 GB)"));
+
+    assert_eq!(handle.join().expect("stub thread should join"), "GET /api/tags HTTP/1.1");
+}
+
+#[test]
+#[serial]
+fn ollama_pull_prints_progress_percentages() {
+    let _ctx = CliTestContext::new();
+    let (port, handle) = serve_once(
+        "200 OK",
+        concat!(
+            "{\"status\":\"pulling manifest\"}\n",
+            "{\"status\":\"pulling abc\",\"completed\":50,\"total\":200}\n",
+            "{\"status\":\"pulling abc\",\"completed\":200,\"total\":200}\n",
+            "{\"status\":\"success\"}\n",
+        ),
+    );
+    let mut cfg = load_config().expect("load_config should succeed");
+    cfg.ollama_server.port = port;
+    save_config(&cfg).expect("save_config should succeed");
+
+    Command::cargo_bin("fusion")
+        .unwrap()
+        .args(["ollama", "pull", "llama3.2:3b"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("pulling manifest"))
+        .stdout(predicate::str::contains("pulling abc: 25%"))
+        .stdout(predicate::str::contains("pulling abc: 100%"))
+        .stdout(predicate::str::contains("✅ Pulled llama3.2:3b"));
+
+    assert_eq!(handle.join().expect("stub thread should join"), "POST /api/pull HTTP/1.1");
+}
+
+#[test]
+#[serial]
+fn ollama_api_passthrough_pretty_prints_json() {
+    let _ctx = CliTestContext::new();
+    let (port, handle) = serve_once("200 OK", r#"{"models":[{"name":"llama3.2:3b"}]}"#);
+    let mut cfg = load_config().expect("load_config should succeed");
+    cfg.ollama_server.port = port;
+    save_config(&cfg).expect("save_config should succeed");
+
+    Command::cargo_bin("fusion")
+        .unwrap()
+        .args(["ollama", "api", "get", "/api/tags"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"name\": \"llama3.2:3b\""));
+
+    assert_eq!(handle.join().expect("stub thread should join"), "GET /api/tags HTTP/1.1");
+}
+
+#[test]
+#[serial]
+fn api_passthrough_rejects_unknown_methods_and_bad_bodies() {
+    let _ctx = CliTestContext::new();
+    for args in [["ollama", "api", "TRACE", "/api/ps"], ["ollama", "api", "GET", "api/ps"]] {
+        Command::cargo_bin("fusion").unwrap().args(args).assert().code(2);
+    }
+    Command::cargo_bin("fusion")
+        .unwrap()
+        .args(["ollama", "api", "POST", "/api/show", "--body", "{not json"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("not valid JSON"));
+}
+
+#[test]
+#[serial]
+fn ollama_show_prints_model_details_for_the_configured_model() {
+    let _ctx = CliTestContext::new();
+    let (port, handle) = serve_once(
+        "200 OK",
+        r#"{"license":"LLAMA 3.2 COMMUNITY LICENSE AGREEMENT\nMore text","parameters":"stop \"<|eot_id|>\"\ntemperature 0.6","template":"{{ .System }}\n{{ .Prompt }}","details":{"format":"gguf","family":"llama","parameter_size":"3.2B","quantization_level":"Q4_K_M"},"model_info":{"general.parameter_count":3212749888}}"#,
+    );
+    let mut cfg = load_config().expect("load_config should succeed");
+    cfg.ollama_server.port = port;
+    save_config(&cfg).expect("save_config should succeed");
+
+    Command::cargo_bin("fusion")
+        .unwrap()
+        .args(["ollama", "show"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("📄 llama3.2:3b:"))
+        .stdout(predicate::str::contains("• parameters: 3.2B (3212749888)"))
+        .stdout(predicate::str::contains("• quantization: Q4_K_M"))
+        .stdout(predicate::str::contains("• license: LLAMA 3.2 COMMUNITY LICENSE AGREEMENT\n"))
+        .stdout(predicate::str::contains("    temperature 0.6"));
+
+    assert_eq!(handle.join().expect("stub thread should join"), "POST /api/show HTTP/1.1");
+}
@@ -1,10 +1,10 @@
 mod common;
 
 use common::CliTestContext;
-use fusion::cli::{self, ServiceType};
+use fusion::cli::{self, DownOptions, ServiceType, StatusFilter, UpOptions};
 use fusion::core::config::{load_config, save_config};
-use fusion::core::process::{DriverGuard, ProcessDriver, install_driver};
-use fusion::core::services::ManagedService;
+use fusion::core::process::{DriverGuard, ProcessDriver, StatusOutcome, install_driver};
+use fusion::core::services::{self, ManagedService};
 use fusion::error::AppError;
 use serial_test::serial;
 use std::collections::HashSet;
@@ -17,6 +17,8 @@ struct DriverState {
     next_pid: i32,
     running: HashSet<String>,
     events: Vec<String>,
+    /// Errors the next spawns fail with, in order, before spawning succeeds again.
+    failing_spawns: Vec<std::io::ErrorKind>,
 }
 
 #[derive(Clone)]
@@ -31,6 +33,7 @@ impl MockDriver {
                 next_pid: 10_000,
                 running: HashSet::new(),
                 events: Vec::new(),
+                failing_spawns: Vec::new(),
             })),
         }
     }
@@ -40,6 +43,18 @@ impl MockDriver {
         state.events.clone()
     }
 
+    /// Mark a service as running without a PID file, so only a signature lookup finds it.
+    fn mark_running(&self, name: &str) {
+        let mut state = self.state.lock().expect("driver state poisoned");
+        state.running.insert(name.to_string());
+    }
+
+    /// Make the next spawn fail with `kind` before any process starts.
+    fn fail_next_spawn(&self, kind: std::io::ErrorKind) {
+        let mut state = self.state.lock().expect("driver state poisoned");
+        state.failing_spawns.push(kind);
+    }
+
     fn reset_events(&self) {
         let mut state = self.state.lock().expect("driver state poisoned");
         state.events.clear();
@@ -53,6 +68,11 @@ impl ProcessDriver for MockDriver {
         _log_path: &std::path::Path,
     ) -> Result<i32, AppError> {
         let mut state = self.state.lock().expect("driver state poisoned");
+        if !state.failing_spawns.is_empty() {
+            let kind = state.failing_spawns.remove(0);
+            state.events.push(format!("spawn-failed:{}", service.name));
+            return Err(AppError::from(std::io::Error::from(kind)));
+        }
         let pid = state.next_pid;
         state.next_pid += 1;
         state.running.insert(service.name.to_string());
@@ -83,16 +103,8 @@ impl ProcessDriver for MockDriver {
         Ok(removed)
     }
 
-    fn kill_by_signature(&self, service: &ManagedService, force: bool) -> Result<usize, AppError> {
-        let mut state = self.state.lock().expect("driver state poisoned");
-        let was_running = state.running.remove(service.name);
-        if was_running {
-            state.events.push(format!("kill:{}:{}", service.name, force));
-            Ok(1)
-        } else {
-            state.events.push(format!("kill-miss:{}:{}", service.name, force));
-            Ok(0)
-        }
+    fn health_port_open(&self, _service: &ManagedService) -> bool {
+        false
     }
 }
 
@@ -103,47 +115,58 @@ fn install_mock_driver() -> (DriverGuard, MockDriver) {
 }
 
 fn start_health_stub() -> (u16, thread::JoinHandle<()>) {
+    start_health_stub_serving(1)
+}
+
+/// Health stub that answers `requests` inference probes, one per connection.
+fn start_health_stub_serving(requests: usize) -> (u16, thread::JoinHandle<()>) {
     let listener = TcpListener::bind("127.0.0.1:0").expect("stub listener should bind");
     let port = listener.local_addr().unwrap().port();
 
     let handle = thread::spawn(move || {
-        let (stream, _) = listener.accept().expect("accept should succeed");
-        let mut reader = BufReader::new(stream);
-
-        let mut request_line = String::new();
-        reader.read_line(&mut request_line).expect("read request line");
-
-        let mut content_length = 0usize;
-        loop {
-            let mut header = String::new();
-            reader.read_line(&mut header).expect("read header");
-            if header.trim().is_empty() {
-                break;
-            }
-            let lower = header.to_ascii_lowercase();
-            if let Some(value) = header.split(':').nth(1)
-                && lower.starts_with("content-length")
-            {
-                content_length = value.trim().parse::<usize>().expect("parse content length");
-            }
+        for _ in 0..requests {
+            answer_health_probe(&listener);
         }
+    });
+
+    (port, handle)
+}
+
+fn answer_health_probe(listener: &TcpListener) {
+    let (stream, _) = listener.accept().expect("accept should succeed");
+    let mut reader = BufReader::new(stream);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).expect("read request line");
 
-        if content_length > 0 {
-            let mut body = vec![0u8; content_length];
-            reader.read_exact(&mut body).expect("read body");
+    let mut content_length = 0usize;
+    loop {
+        let mut header = String::new();
+        reader.read_line(&mut header).expect("read header");
+        if header.trim().is_empty() {
+            break;
         }
+        let lower = header.to_ascii_lowercase();
+        if let Some(value) = header.split(':').nth(1)
+            && lower.starts_with("content-length")
+        {
+            content_length = value.trim().parse::<usize>().expect("parse content length");
+        }
+    }
 
-        let response_body = br#"{"choices":[{"message":{"role":"assistant","content":"ready"}}]}"#;
-        let response = format!(
-            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
-            response_body.len(),
-            String::from_utf8_lossy(response_body)
-        );
-        reader.get_mut().write_all(response.as_bytes()).expect("write response");
-        reader.get_mut().flush().ok();
-    });
+    if content_length > 0 {
+        let mut body = vec![0u8; content_length];
+        reader.read_exact(&mut body).expect("read body");
+    }
 
-    (port, handle)
+    let response_body = br#"{"choices":[{"message":{"role":"assistant","content":"ready"}}]}"#;
+    let response = format!(
+        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+        response_body.len(),
+        String::from_utf8_lossy(response_body)
+    );
+    reader.get_mut().write_all(response.as_bytes()).expect("write response");
+    reader.get_mut().flush().ok();
 }
 
 #[test]
@@ -182,6 +205,150 @@ fn llm_mlx_up_starts_service() {
     handle.join().expect("stub thread should join");
 }
 
+#[test]
+#[serial]
+fn up_retries_a_transient_spawn_failure() {
+    let _ctx = CliTestContext::new();
+    let (port, handle) = start_health_stub();
+    let mut cfg = load_config().expect("load_config should succeed");
+    cfg.ollama_server.port = port;
+    save_config(&cfg).expect("save_config should succeed");
+
+    let (_guard, driver) = install_mock_driver();
+    driver.fail_next_spawn(std::io::ErrorKind::ExecutableFileBusy);
+    cli::handle_up(ServiceType::Ollama).expect("up should succeed on the second spawn");
+
+    let events = driver.events();
+    let spawns: Vec<_> =
+        events.iter().filter(|e| e.ends_with(":ollama") && !e.starts_with("status")).collect();
+    assert_eq!(spawns, ["spawn-failed:ollama", "start:ollama"], "events: {events:?}");
+
+    handle.join().expect("stub thread should join");
+}
+
+#[test]
+#[serial]
+fn exhausted_spawn_retries_fail_as_a_process_error() {
+    let _ctx = CliTestContext::new();
+    let (_guard, driver) = install_mock_driver();
+    for _ in 0..3 {
+        driver.fail_next_spawn(std::io::ErrorKind::ExecutableFileBusy);
+    }
+
+    let err = cli::handle_up(ServiceType::Ollama).expect_err("every spawn attempt fails");
+
+    assert_eq!(err.exit_code(), 3, "{err}");
+    let events = driver.events();
+    assert_eq!(events.iter().filter(|e| *e == "spawn-failed:ollama").count(), 3, "{events:?}");
+}
+
+#[test]
+#[serial]
+fn up_does_not_retry_a_permanent_spawn_failure() {
+    let _ctx = CliTestContext::new();
+    let (_guard, driver) = install_mock_driver();
+    driver.fail_next_spawn(std::io::ErrorKind::NotFound);
+    driver.fail_next_spawn(std::io::ErrorKind::NotFound);
+
+    cli::handle_up(ServiceType::Ollama).expect_err("a missing binary should fail the start");
+
+    let events = driver.events();
+    let attempts = events.iter().filter(|e| *e == "spawn-failed:ollama").count();
+    assert_eq!(attempts, 1, "events: {events:?}");
+}
+
+#[test]
+#[serial]
+fn llm_up_dry_run_does_not_spawn() {
+    let _ctx = CliTestContext::new();
+    let (_guard, driver) = install_mock_driver();
+
+    let options = UpOptions { dry_run: true, ..UpOptions::default() };
+    cli::handle_up_with_options(ServiceType::Mlx, &options).expect("mlx dry run should succeed");
+    cli::handle_up_with_options(ServiceType::Ollama, &options)
+        .expect("ollama dry run should succeed");
+
+    let events = driver.events();
+    assert!(events.iter().all(|e| !e.starts_with("start:")), "unexpected spawn: {events:?}");
+}
+
+#[test]
+#[serial]
+fn llm_up_zero_timeout_skips_readiness_polling() {
+    let _ctx = CliTestContext::new();
+    // Nothing listens on this port, so any readiness probe would fail the command.
+    let unused_port = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+    let mut cfg = load_config().expect("load_config should succeed");
+    cfg.ollama_server.port = unused_port;
+    save_config(&cfg).expect("save_config should succeed");
+
+    let (_guard, driver) = install_mock_driver();
+    let options = UpOptions { timeout_secs: Some(0), ..UpOptions::default() };
+    cli::handle_up_with_options(ServiceType::Ollama, &options).expect("ollama up should succeed");
+
+    let events = driver.events();
+    assert!(events.iter().any(|e| e == "start:ollama"), "events: {events:?}");
+    assert!(events.iter().any(|e| e == "status:ollama"), "liveness should be confirmed");
+}
+
+#[test]
+#[serial]
+fn llm_up_no_wait_makes_no_health_request() {
+    let ctx = CliTestContext::new();
+    let listener = TcpListener::bind("127.0.0.1:0").expect("listener should bind");
+    listener.set_nonblocking(true).unwrap();
+    let mut cfg = load_config().expect("load_config should succeed");
+    cfg.mlx_server.port = listener.local_addr().unwrap().port();
+    save_config(&cfg).expect("save_config should succeed");
+
+    let (_guard, driver) = install_mock_driver();
+    let options = UpOptions { no_wait: true, ..UpOptions::default() };
+    cli::handle_up_with_options(ServiceType::Mlx, &options).expect("mlx up should succeed");
+
+    assert_eq!(driver.events(), ["start:mlx"]);
+    assert!(listener.accept().is_err(), "no health request should reach the server");
+    let sidecar_name = format!("mlx/mlx-{}.config", cfg.mlx_server.port);
+    assert!(ctx.pid_dir().join(&sidecar_name).exists(), "runtime config should be written");
+}
+
+#[test]
+#[serial]
+fn llm_mlx_up_rejects_empty_model_before_spawning() {
+    let _ctx = CliTestContext::new();
+    let mut cfg = load_config().expect("load_config should succeed");
+    cfg.mlx_server.model = "  ".into();
+    save_config(&cfg).expect("save_config should succeed");
+
+    let (_guard, driver) = install_mock_driver();
+    let err = cli::handle_up(ServiceType::Mlx).expect_err("empty model should be rejected");
+    assert!(matches!(err, AppError::ConfigError(_)), "got {err:?}");
+    assert!(err.to_string().contains("mlx_server.model is empty"), "error: {err}");
+    assert!(driver.events().iter().all(|e| !e.starts_with("start:")));
+}
+
+#[test]
+#[serial]
+fn llm_up_port_override_is_recorded_in_sidecar() {
+    let ctx = CliTestContext::new();
+    let (port, handle) = start_health_stub();
+    let cfg = load_config().expect("load_config should succeed");
+    assert_ne!(cfg.mlx_server.port, port);
+
+    let (_guard, driver) = install_mock_driver();
+    let options = UpOptions { port: Some(port), ..UpOptions::default() };
+    cli::handle_up_with_options(ServiceType::Mlx, &options).expect("mlx up should succeed");
+    handle.join().expect("stub thread should join");
+
+    let sidecar = std::fs::read_to_string(ctx.pid_dir().join("mlx/mlx.config"))
+        .expect("runtime sidecar should be written");
+    assert!(sidecar.contains(&format!("port = {port}")), "sidecar: {sidecar}");
+
+    driver.reset_events();
+    cli::handle_down(ServiceType::Mlx, false).expect("mlx down should succeed");
+    assert!(driver.events().iter().any(|e| e == "signal:mlx:false"));
+    assert_eq!(load_config().unwrap().mlx_server.port, cfg.mlx_server.port);
+}
+
 #[test]
 #[serial]
 fn llm_ollama_down_stops_service() {
@@ -202,6 +369,30 @@ fn llm_ollama_down_stops_service() {
     handle.join().expect("stub thread should join");
 }
 
+#[test]
+#[serial]
+fn llm_ollama_down_finds_service_after_port_change() {
+    let ctx = CliTestContext::new();
+    let (port, handle) = start_health_stub();
+    let mut cfg = load_config().expect("load_config should succeed");
+    cfg.ollama_server.port = port;
+    save_config(&cfg).expect("save_config should succeed");
+
+    let (_guard, driver) = install_mock_driver();
+    cli::handle_up(ServiceType::Ollama).expect("ollama up should succeed");
+    handle.join().expect("stub thread should join");
+    let pid_file = ctx.pid_dir().join(format!("ollama/ollama-{port}.pid"));
+    assert!(pid_file.exists(), "pid file should be recorded under the start port");
+
+    cfg.ollama_server.port = port.wrapping_add(1).max(1024);
+    save_config(&cfg).expect("save_config should succeed");
+    driver.reset_events();
+    cli::handle_down(ServiceType::Ollama, false).expect("ollama down should succeed");
+
+    assert!(driver.events().iter().any(|e| e == "signal:ollama:false"));
+    assert!(!pid_file.exists(), "pid file should be removed once stopped");
+}
+
 #[test]
 #[serial]
 fn llm_mlx_down_stops_service() {
@@ -224,7 +415,7 @@ fn llm_mlx_down_stops_service() {
 
 #[test]
 #[serial]
-fn llm_force_down_kills_when_not_running() {
+fn llm_force_down_signals_nothing_when_not_running() {
     let _ctx = CliTestContext::new();
     let (_guard, driver) = install_mock_driver();
 
@@ -232,8 +423,9 @@ fn llm_force_down_kills_when_not_running() {
     cli::handle_down(ServiceType::Mlx, true).expect("force down for mlx should not error");
 
     let events = driver.events();
-    assert!(events.iter().any(|e| e == "kill-miss:ollama:true"));
-    assert!(events.iter().any(|e| e == "kill-miss:mlx:true"));
+    assert!(events.iter().any(|e| e == "status-by-sig:ollama"));
+    assert!(events.iter().any(|e| e == "status-by-sig:mlx"));
+    assert!(events.iter().all(|e| !e.starts_with("signal:")), "events: {events:?}");
 }
 
 #[test]
@@ -278,6 +470,80 @@ fn llm_ollama_ps_queries_one_service() {
     handle.join().expect("stub thread should join");
 }
 
+#[test]
+#[serial]
+fn ps_filter_lists_only_services_in_that_state() {
+    let _ctx = CliTestContext::new();
+    let (ollama_port, handle_ollama) = start_health_stub();
+    let mut cfg = load_config().expect("load_config should succeed");
+    cfg.ollama_server.port = ollama_port;
+    save_config(&cfg).expect("save_config should succeed");
+
+    let (_guard, _driver) = install_mock_driver();
+    cli::handle_up(ServiceType::Ollama).expect("ollama up should succeed");
+
+    let names = |filter| {
+        cli::service_statuses(Some(filter))
+            .expect("statuses should load")
+            .into_iter()
+            .map(|(service, _)| service.name)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(names(StatusFilter::Running), ["ollama"]);
+    assert_eq!(names(StatusFilter::Stopped), ["mlx"]);
+    assert_eq!(cli::service_statuses(None).expect("statuses should load").len(), 2);
+
+    handle_ollama.join().expect("stub thread should join");
+}
+
+#[test]
+#[serial]
+fn ps_porcelain_columns_are_tab_separated_in_fixed_order() {
+    let _ctx = CliTestContext::new();
+    let (ollama_port, handle_ollama) = start_health_stub();
+    let mut cfg = load_config().expect("load_config should succeed");
+    cfg.ollama_server.port = ollama_port;
+    save_config(&cfg).expect("save_config should succeed");
+
+    let (_guard, _driver) = install_mock_driver();
+    cli::handle_up(ServiceType::Ollama).expect("ollama up should succeed");
+
+    let statuses = cli::service_statuses(None).expect("statuses should load");
+    let output = cli::render_porcelain(&statuses);
+    let lines: Vec<Vec<&str>> = output.lines().map(|line| line.split('\t').collect()).collect();
+    assert_eq!(lines.len(), 2);
+
+    let ollama = lines.iter().find(|fields| fields[0] == "ollama").expect("ollama line");
+    assert_eq!(ollama.len(), 5);
+    assert_eq!(ollama[1], "running");
+    assert!(ollama[2].parse::<i32>().is_ok(), "pid column: {}", ollama[2]);
+    assert_eq!(ollama[3], "127.0.0.1");
+    assert_eq!(ollama[4], ollama_port.to_string());
+
+    let mlx = lines.iter().find(|fields| fields[0] == "mlx").expect("mlx line");
+    assert_eq!(&mlx[1..3], ["stopped", "-"]);
+
+    handle_ollama.join().expect("stub thread should join");
+}
+
+#[test]
+#[serial]
+fn short_status_is_one_line_for_mixed_states() {
+    let _ctx = CliTestContext::new();
+    let (ollama_port, handle_ollama) = start_health_stub();
+    let mut cfg = load_config().expect("load_config should succeed");
+    cfg.ollama_server.port = ollama_port;
+    save_config(&cfg).expect("save_config should succeed");
+
+    let (_guard, _driver) = install_mock_driver();
+    cli::handle_up(ServiceType::Ollama).expect("ollama up should succeed");
+
+    let statuses = cli::service_statuses(None).expect("statuses should load");
+    assert_eq!(cli::render_short_status(&statuses), "ollama:up mlx:down");
+
+    handle_ollama.join().expect("stub thread should join");
+}
+
 #[test]
 #[serial]
 fn llm_global_ps_queries_all_services() {
@@ -311,3 +577,154 @@ fn llm_log_reports_paths() {
     cli::handle_logs().expect("handle_logs should succeed");
     assert!(ctx.pid_dir().exists(), "log directory should be created");
 }
+
+#[test]
+#[serial]
+fn ensure_running_starts_once_then_reuses_running_service() {
+    let _ctx = CliTestContext::new();
+    let (port, handle) = start_health_stub_serving(2);
+    let mut cfg = load_config().expect("load_config should succeed");
+    cfg.ollama_server.port = port;
+    save_config(&cfg).expect("save_config should succeed");
+
+    let (_guard, driver) = install_mock_driver();
+    let outcome = services::ensure_running(ServiceType::Ollama).expect("first call should start");
+    assert!(matches!(outcome, StatusOutcome::Running { .. }), "got {outcome:?}");
+    assert_eq!(driver.events().iter().filter(|e| *e == "start:ollama").count(), 1);
+
+    driver.reset_events();
+    let outcome = services::ensure_running(ServiceType::Ollama).expect("second call should no-op");
+    assert!(matches!(outcome, StatusOutcome::Running { .. }), "got {outcome:?}");
+    assert!(!driver.events().iter().any(|e| e.starts_with("start:")), "{:?}", driver.events());
+
+    handle.join().expect("stub thread should join");
+}
+
+#[test]
+#[serial]
+fn ensure_running_applies_the_same_config_checks_as_up() {
+    let _ctx = CliTestContext::new();
+    let mut cfg = load_config().expect("load_config should succeed");
+    cfg.ollama_server.host = "0.0.0.0".to_string();
+    save_config(&cfg).expect("save_config should succeed");
+
+    let (_guard, driver) = install_mock_driver();
+    fusion::core::config::set_strict(true);
+    let result = services::ensure_running(ServiceType::Ollama);
+    fusion::core::config::set_strict(false);
+
+    let err = result.expect_err("a public bind should be rejected under --strict");
+    assert_eq!(err.exit_code(), 2, "{err}");
+    assert!(driver.events().iter().all(|e| !e.starts_with("start:")), "{:?}", driver.events());
+}
+
+#[test]
+#[serial]
+fn logs_prints_every_service_tail() {
+    let ctx = CliTestContext::new();
+    for (service, line) in [("ollama", "ollama failed to bind"), ("mlx", "mlx out of memory")] {
+        let dir = ctx.pid_dir().join(service);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join(format!("{service}.log")), format!("starting\n{line}\n")).unwrap();
+    }
+
+    assert_cmd::Command::cargo_bin("fusion")
+        .unwrap()
+        .arg("logs")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("ollama failed to bind"))
+        .stdout(predicates::str::contains("mlx out of memory"));
+
+    std::fs::remove_file(ctx.pid_dir().join("mlx/mlx.log")).unwrap();
+    assert_cmd::Command::cargo_bin("fusion")
+        .unwrap()
+        .arg("logs")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("ollama failed to bind"))
+        .stdout(predicates::str::contains("(log file not found)"));
+}
+
+#[test]
+#[serial]
+fn force_down_with_yes_kills_signature_matches_without_prompting() {
+    let _ctx = CliTestContext::new();
+    let (_guard, driver) = install_mock_driver();
+    driver.mark_running("ollama");
+
+    let options = DownOptions { force: true, yes: true, ..DownOptions::default() };
+    cli::handle_down_with_options(ServiceType::Ollama, &options).expect("force down should work");
+
+    assert!(driver.events().iter().any(|e| e == "signal:ollama:true"), "{:?}", driver.events());
+}
+
+#[cfg(unix)]
+#[test]
+#[serial]
+fn server_and_log_relays_survive_signals_to_the_spawning_process_group() {
+    use std::os::unix::fs::PermissionsExt;
+    use std::os::unix::process::CommandExt;
+    use sysinfo::{Pid, ProcessStatus, System};
+
+    let ctx = CliTestContext::new();
+    let bin = ctx.root.path().join("bin");
+    std::fs::create_dir_all(&bin).unwrap();
+    // Writes to stdout constantly, so it dies of SIGPIPE as soon as its relay is gone.
+    let server = bin.join("ollama");
+    std::fs::write(&server, "#!/bin/sh\nwhile :; do echo tick; sleep 0.1; done\n").unwrap();
+    std::fs::set_permissions(&server, std::fs::Permissions::from_mode(0o755)).unwrap();
+    let path = format!("{}:{}", bin.display(), std::env::var("PATH").unwrap_or_default());
+
+    // `up` leads its own process group, the one Ctrl-C or a terminal hangup would signal.
+    let child = std::process::Command::new(assert_cmd::cargo::cargo_bin("fusion"))
+        .env("PATH", path)
+        .args(["ollama", "up", "--no-wait"])
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .process_group(0)
+        .spawn()
+        .expect("fusion should run");
+    let group = child.id() as i32;
+    let output = child.wait_with_output().expect("fusion should exit");
+    assert!(output.status.success(), "up failed: {}", String::from_utf8_lossy(&output.stderr));
+    let server_pid: i32 = String::from_utf8_lossy(&output.stdout).trim().parse().unwrap();
+
+    let root = ctx.root.path().to_string_lossy().into_owned();
+    let mut system = System::new_all();
+    let relays: Vec<Pid> = system
+        .processes()
+        .iter()
+        .filter(|(_, process)| {
+            let cmd = process.cmd();
+            cmd.get(1).is_some_and(|arg| arg == "__stamp-log")
+                && cmd.get(2).is_some_and(|arg| arg.starts_with(&root))
+        })
+        .map(|(pid, _)| *pid)
+        .collect();
+    assert_eq!(relays.len(), 2, "expected a stdout and a stderr relay");
+
+    // SAFETY: signals a process group created for this test only.
+    unsafe {
+        libc::kill(-group, libc::SIGINT);
+        libc::kill(-group, libc::SIGHUP);
+    }
+    std::thread::sleep(std::time::Duration::from_millis(500));
+
+    system.refresh_processes();
+    let alive = |pid: Pid| {
+        system.process(pid).is_some_and(|process| process.status() != ProcessStatus::Zombie)
+    };
+    let survivors = relays.iter().filter(|pid| alive(**pid)).count();
+    let server_alive = alive(Pid::from_u32(server_pid as u32));
+
+    // SAFETY: stops processes this test started.
+    unsafe {
+        libc::kill(server_pid, libc::SIGKILL);
+        for relay in &relays {
+            libc::kill(relay.as_u32() as i32, libc::SIGKILL);
+        }
+    }
+    assert!(server_alive, "server should keep running");
+    assert_eq!(survivors, 2, "log relays should keep running");
+}
@@ -0,0 +1,24 @@
+mod common;
+
+use assert_cmd::Command;
+use common::CliTestContext;
+
+#[test]
+#[serial_test::serial]
+fn version_json_reports_crate_version_and_config_path() {
+    let ctx = CliTestContext::new();
+    let output = Command::cargo_bin("fusion")
+        .unwrap()
+        .args(["version", "--json"])
+        .output()
+        .expect("fusion version should run");
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+
+    let info: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("version output should be JSON");
+    assert_eq!(info["version"], env!("CARGO_PKG_VERSION"));
+    assert!(info["git_commit"].is_string());
+    assert!(info["rustc"].as_str().is_some_and(|rustc| rustc.starts_with("rustc")));
+    let config_path = ctx.pid_dir().join("config.toml");
+    assert_eq!(info["config_path"], config_path.display().to_string());
+}